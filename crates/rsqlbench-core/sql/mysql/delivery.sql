@@ -0,0 +1,54 @@
+CREATE PROCEDURE `DELIVERY`(
+    d_w_id INTEGER,
+    d_o_carrier_id INTEGER,
+    IN timestamp DATETIME
+)
+BEGIN
+    DECLARE d_no_o_id INTEGER;
+    DECLARE current_rowid INTEGER;
+    DECLARE d_d_id INTEGER;
+    DECLARE d_c_id INTEGER;
+    DECLARE d_ol_total INTEGER;
+    DECLARE deliv_data VARCHAR(100);
+    DECLARE loop_counter INT;
+    DECLARE `Constraint Violation` CONDITION FOR SQLSTATE '23000';
+    DECLARE EXIT HANDLER FOR `Constraint Violation` ROLLBACK;
+    SET loop_counter = 1;
+    START TRANSACTION;
+    WHILE loop_counter <= 10
+        DO
+            SET d_d_id = loop_counter;
+            SELECT no_o_id INTO d_no_o_id FROM new_order WHERE no_w_id = d_w_id AND no_d_id = d_d_id LIMIT 1;
+            DELETE FROM new_order WHERE no_w_id = d_w_id AND no_d_id = d_d_id AND no_o_id = d_no_o_id;
+            SELECT o_c_id
+            INTO d_c_id
+            FROM oorder
+            WHERE o_id = d_no_o_id
+              AND o_d_id = d_d_id
+              AND o_w_id = d_w_id;
+            UPDATE oorder
+            SET o_carrier_id = d_o_carrier_id
+            WHERE o_id = d_no_o_id
+              AND o_d_id = d_d_id
+              AND o_w_id = d_w_id;
+            UPDATE order_line
+            SET ol_delivery_d = timestamp
+            WHERE ol_o_id = d_no_o_id
+              AND ol_d_id = d_d_id
+              AND ol_w_id = d_w_id;
+            SELECT SUM(ol_amount)
+            INTO d_ol_total
+            FROM order_line
+            WHERE ol_o_id = d_no_o_id
+              AND ol_d_id = d_d_id
+              AND ol_w_id = d_w_id;
+            UPDATE customer
+            SET c_balance = c_balance + d_ol_total
+            WHERE c_id = d_c_id
+              AND c_d_id = d_d_id
+              AND c_w_id = d_w_id;
+            SET deliv_data = CONCAT(d_d_id, ' ', d_no_o_id, ' ', timestamp);
+            set loop_counter = loop_counter + 1;
+        END WHILE;
+    COMMIT;
+END
@@ -0,0 +1,2 @@
+CREATE INDEX customer_i2 ON customer (c_w_id, c_d_id, c_last, c_first);
+CREATE INDEX oorder_i2 ON oorder (o_w_id, o_d_id, o_c_id, o_id);
@@ -0,0 +1,162 @@
+CREATE PROCEDURE `NEWORD`(
+    no_w_id INTEGER,
+    no_max_w_id INTEGER,
+    no_d_id INTEGER,
+    no_c_id INTEGER,
+    no_o_ol_cnt INTEGER,
+    OUT no_c_discount DECIMAL(4, 4),
+    OUT no_c_last VARCHAR(16),
+    OUT no_c_credit VARCHAR(2),
+    OUT no_d_tax DECIMAL(4, 4),
+    OUT no_w_tax DECIMAL(4, 4),
+    INOUT no_d_next_o_id INTEGER,
+    IN timestamp DATETIME
+)
+BEGIN
+    DECLARE no_ol_supply_w_id INTEGER;
+    DECLARE no_ol_i_id INTEGER;
+    DECLARE no_ol_quantity INTEGER;
+    DECLARE no_o_all_local INTEGER;
+    DECLARE o_id INTEGER;
+    DECLARE no_i_name VARCHAR(24);
+    DECLARE no_i_price DECIMAL(5, 2);
+    DECLARE no_i_data VARCHAR(50);
+    DECLARE no_s_quantity DECIMAL(6);
+    DECLARE no_ol_amount DECIMAL(6, 2);
+    DECLARE no_s_dist_01 CHAR(24);
+    DECLARE no_s_dist_02 CHAR(24);
+    DECLARE no_s_dist_03 CHAR(24);
+    DECLARE no_s_dist_04 CHAR(24);
+    DECLARE no_s_dist_05 CHAR(24);
+    DECLARE no_s_dist_06 CHAR(24);
+    DECLARE no_s_dist_07 CHAR(24);
+    DECLARE no_s_dist_08 CHAR(24);
+    DECLARE no_s_dist_09 CHAR(24);
+    DECLARE no_s_dist_10 CHAR(24);
+    DECLARE no_ol_dist_info CHAR(24);
+    DECLARE no_s_data VARCHAR(50);
+    DECLARE x INTEGER;
+    DECLARE rbk INTEGER;
+    DECLARE loop_counter INT;
+    DECLARE no_max_i_id INTEGER;
+    DECLARE remote_lines INTEGER;
+    DECLARE `Constraint Violation` CONDITION FOR SQLSTATE '23000';
+    DECLARE EXIT HANDLER FOR `Constraint Violation` ROLLBACK;
+    DECLARE EXIT HANDLER FOR NOT FOUND ROLLBACK;
+    SET no_o_all_local = 1;
+    SELECT c_discount, c_last, c_credit, w_tax
+    INTO no_c_discount, no_c_last, no_c_credit, no_w_tax
+    FROM customer,
+         warehouse
+    WHERE warehouse.w_id = no_w_id
+      AND customer.c_w_id = no_w_id
+      AND customer.c_d_id = no_d_id
+      AND customer.c_id = no_c_id;
+    START TRANSACTION;
+    SELECT d_next_o_id, d_tax
+    INTO no_d_next_o_id, no_d_tax
+    FROM district
+    WHERE d_id = no_d_id
+      AND d_w_id = no_w_id FOR
+    UPDATE;
+    UPDATE district SET d_next_o_id = d_next_o_id + 1 WHERE d_id = no_d_id AND d_w_id = no_w_id;
+    SET o_id = no_d_next_o_id;
+    SET rbk = FLOOR(1 + (RAND() * 99));
+    SET no_max_i_id = {{items}};
+    -- Lines supplied by remote warehouses are picked first, so that o_all_local is known before inserting order.
+    SET remote_lines = 0;
+    SET loop_counter = 1;
+    WHILE loop_counter <= no_o_ol_cnt
+        DO
+            SET x = FLOOR(1 + (RAND() * 100));
+            IF (x = 1)
+            THEN
+                SET remote_lines = remote_lines | (1 << loop_counter);
+                SET no_o_all_local = 0;
+            END IF;
+            SET loop_counter = loop_counter + 1;
+        END WHILE;
+    -- Order lines reference order by foreign key.
+    IF ({{foreign_keys}} = 1)
+    THEN
+        INSERT INTO oorder (o_id, o_d_id, o_w_id, o_c_id, o_entry_d, o_ol_cnt, o_all_local)
+        VALUES (o_id, no_d_id, no_w_id, no_c_id, timestamp, no_o_ol_cnt, no_o_all_local);
+        INSERT INTO new_order (no_o_id, no_d_id, no_w_id) VALUES (o_id, no_d_id, no_w_id);
+    END IF;
+    SET loop_counter = 1;
+    WHILE loop_counter <= no_o_ol_cnt
+        DO
+            IF ((loop_counter = no_o_ol_cnt) AND (rbk = 1))
+            THEN
+                SET no_ol_i_id = no_max_i_id + 1;
+            ELSE
+                SET no_ol_i_id = FLOOR(1 + (RAND() * no_max_i_id));
+            END IF;
+            IF (((remote_lines >> loop_counter) & 1) = 0)
+            THEN
+                SET no_ol_supply_w_id = no_w_id;
+            ELSE
+                SET no_ol_supply_w_id = no_w_id;
+                WHILE ((no_ol_supply_w_id = no_w_id) AND (no_max_w_id != 1))
+                    DO
+                        SET no_ol_supply_w_id = FLOOR(1 + (RAND() * no_max_w_id));
+                    END WHILE;
+            END IF;
+            SET no_ol_quantity = FLOOR(1 + (RAND() * 10));
+            SELECT i_price, i_name, i_data
+            INTO no_i_price, no_i_name, no_i_data
+            FROM item
+            WHERE i_id = no_ol_i_id;
+            SELECT s_quantity,
+                   s_data,
+                   s_dist_01,
+                   s_dist_02,
+                   s_dist_03,
+                   s_dist_04,
+                   s_dist_05,
+                   s_dist_06,
+                   s_dist_07,
+                   s_dist_08,
+                   s_dist_09,
+                   s_dist_10
+            INTO no_s_quantity, no_s_data, no_s_dist_01, no_s_dist_02, no_s_dist_03, no_s_dist_04, no_s_dist_05, no_s_dist_06, no_s_dist_07, no_s_dist_08, no_s_dist_09, no_s_dist_10
+            FROM stock
+            WHERE s_i_id = no_ol_i_id
+              AND s_w_id = no_ol_supply_w_id;
+            IF (no_s_quantity > no_ol_quantity)
+            THEN
+                SET no_s_quantity = (no_s_quantity - no_ol_quantity);
+            ELSE
+                SET no_s_quantity = (no_s_quantity - no_ol_quantity + 91);
+            END IF;
+            UPDATE stock
+            SET s_quantity = no_s_quantity
+            WHERE s_i_id = no_ol_i_id
+              AND s_w_id = no_ol_supply_w_id;
+            SET no_ol_amount = (no_ol_quantity * no_i_price * (1 + no_w_tax + no_d_tax) * (1 - no_c_discount));
+            CASE no_d_id
+                WHEN 1 THEN SET no_ol_dist_info = no_s_dist_01;
+                WHEN 2 THEN SET no_ol_dist_info = no_s_dist_02;
+                WHEN 3 THEN SET no_ol_dist_info = no_s_dist_03;
+                WHEN 4 THEN SET no_ol_dist_info = no_s_dist_04;
+                WHEN 5 THEN SET no_ol_dist_info = no_s_dist_05;
+                WHEN 6 THEN SET no_ol_dist_info = no_s_dist_06;
+                WHEN 7 THEN SET no_ol_dist_info = no_s_dist_07;
+                WHEN 8 THEN SET no_ol_dist_info = no_s_dist_08;
+                WHEN 9 THEN SET no_ol_dist_info = no_s_dist_09;
+                WHEN 10 THEN SET no_ol_dist_info = no_s_dist_10;
+                END CASE;
+            INSERT INTO order_line (ol_o_id, ol_d_id, ol_w_id, ol_number, ol_i_id, ol_supply_w_id, ol_quantity,
+                                    ol_amount, ol_dist_info)
+            VALUES (o_id, no_d_id, no_w_id, loop_counter, no_ol_i_id, no_ol_supply_w_id, no_ol_quantity, no_ol_amount,
+                    no_ol_dist_info);
+            set loop_counter = loop_counter + 1;
+        END WHILE;
+    IF ({{foreign_keys}} = 0)
+    THEN
+        INSERT INTO oorder (o_id, o_d_id, o_w_id, o_c_id, o_entry_d, o_ol_cnt, o_all_local)
+        VALUES (o_id, no_d_id, no_w_id, no_c_id, timestamp, no_o_ol_cnt, no_o_all_local);
+        INSERT INTO new_order (no_o_id, no_d_id, no_w_id) VALUES (o_id, no_d_id, no_w_id);
+    END IF;
+    COMMIT;
+END
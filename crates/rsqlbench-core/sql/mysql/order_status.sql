@@ -0,0 +1,107 @@
+CREATE PROCEDURE `OSTAT`(
+    os_w_id INTEGER,
+    os_d_id INTEGER,
+    INOUT os_c_id INTEGER,
+    byname INTEGER,
+    INOUT os_c_last VARCHAR(16),
+    OUT os_c_first VARCHAR(16),
+    OUT os_c_middle CHAR(2),
+    OUT os_c_balance DECIMAL(12, 2),
+    OUT os_o_id INTEGER,
+    OUT os_entdate DATETIME,
+    OUT os_o_carrier_id INTEGER,
+    OUT os_ol_lines VARCHAR(1024)
+)
+BEGIN
+    DECLARE os_ol_i_id INTEGER;
+    DECLARE os_ol_supply_w_id INTEGER;
+    DECLARE os_ol_quantity INTEGER;
+    DECLARE os_ol_amount DECIMAL(6, 2);
+    DECLARE os_ol_delivery_d DATETIME;
+    DECLARE done INT DEFAULT 0;
+    DECLARE namecnt INTEGER;
+    DECLARE i INTEGER;
+    DECLARE loop_counter INT;
+    DECLARE no_order_status VARCHAR(100);
+    DECLARE `Constraint Violation` CONDITION FOR SQLSTATE '23000';
+    DECLARE c_name CURSOR FOR
+        SELECT c_balance, c_first, c_middle, c_id
+        FROM customer
+        WHERE c_last = os_c_last
+          AND c_d_id = os_d_id
+          AND c_w_id = os_w_id
+        ORDER BY c_first;
+    DECLARE c_line CURSOR FOR
+        SELECT ol_i_id,
+               ol_supply_w_id,
+               ol_quantity,
+               ol_amount,
+               ol_delivery_d
+        FROM order_line
+        WHERE ol_o_id = os_o_id
+          AND ol_d_id = os_d_id
+          AND ol_w_id = os_w_id;
+    DECLARE EXIT HANDLER FOR `Constraint Violation` ROLLBACK;
+    DECLARE CONTINUE HANDLER FOR NOT FOUND SET done = 1;
+    set no_order_status = '';
+    -- Lines of `supply_w_id,i_id,quantity,amount,delivery_d` separated by `;`, delivery_d is empty if not delivered.
+    set os_ol_lines = '';
+    START TRANSACTION;
+    IF (byname = 1)
+    THEN
+        SELECT count(c_id)
+        INTO namecnt
+        FROM customer
+        WHERE c_last = os_c_last
+          AND c_d_id = os_d_id
+          AND c_w_id = os_w_id;
+        IF (MOD(namecnt, 2) = 1)
+        THEN
+            SET namecnt = (namecnt + 1);
+        END IF;
+        OPEN c_name;
+        SET loop_counter = 0;
+        WHILE loop_counter <= (namecnt / 2)
+            DO
+                FETCH c_name
+                    INTO os_c_balance, os_c_first, os_c_middle, os_c_id;
+                set loop_counter = loop_counter + 1;
+            END WHILE;
+        close c_name;
+    ELSE
+        SELECT c_balance, c_first, c_middle, c_last
+        INTO os_c_balance, os_c_first, os_c_middle, os_c_last
+        FROM customer
+        WHERE c_id = os_c_id
+          AND c_d_id = os_d_id
+          AND c_w_id = os_w_id;
+    END IF;
+    set done = 0;
+    SELECT o_id, o_carrier_id, o_entry_d
+    INTO os_o_id, os_o_carrier_id, os_entdate
+    FROM (SELECT o_id, o_carrier_id, o_entry_d
+          FROM oorder
+          where o_d_id = os_d_id
+            AND o_w_id = os_w_id
+            and o_c_id = os_c_id
+          ORDER BY o_id DESC) AS sb
+    LIMIT 1;
+    IF done THEN
+        set no_order_status = 'No orders for customer';
+    END IF;
+    set done = 0;
+    set i = 0;
+    OPEN c_line;
+    REPEAT
+        FETCH c_line INTO os_ol_i_id, os_ol_supply_w_id, os_ol_quantity, os_ol_amount, os_ol_delivery_d;
+        IF NOT done THEN
+            set os_ol_lines = CONCAT(os_ol_lines, IF(i = 0, '', ';'),
+                                     CAST(os_ol_supply_w_id AS CHAR), ',', CAST(os_ol_i_id AS CHAR), ',',
+                                     CAST(os_ol_quantity AS CHAR), ',', CAST(os_ol_amount AS CHAR), ',',
+                                     IFNULL(CAST(os_ol_delivery_d AS CHAR), ''));
+            set i = i + 1;
+        END IF;
+    UNTIL done END REPEAT;
+    CLOSE c_line;
+    COMMIT;
+END
@@ -0,0 +1,30 @@
+CREATE PROCEDURE `SLEV`(
+    st_w_id INTEGER,
+    st_d_id INTEGER,
+    threshold INTEGER,
+    OUT stock_count INTEGER
+)
+BEGIN
+    DECLARE st_o_id INTEGER;
+    DECLARE `Constraint Violation` CONDITION FOR SQLSTATE '23000';
+    DECLARE EXIT HANDLER FOR `Constraint Violation` ROLLBACK;
+    DECLARE EXIT HANDLER FOR NOT FOUND ROLLBACK;
+    START TRANSACTION;
+    SELECT d_next_o_id
+    INTO st_o_id
+    FROM district
+    WHERE d_w_id = st_w_id
+      AND d_id = st_d_id;
+    SELECT COUNT(DISTINCT (s_i_id))
+    INTO stock_count
+    FROM order_line,
+         stock
+    WHERE ol_w_id = st_w_id
+      AND ol_d_id = st_d_id
+      AND (ol_o_id < st_o_id)
+      AND ol_o_id >= (st_o_id - 20)
+      AND s_w_id = st_w_id
+      AND s_i_id = ol_i_id
+      AND s_quantity < threshold;
+    COMMIT;
+END
@@ -0,0 +1,24 @@
+CREATE TABLE `customer` (
+  `c_id` INT(5) NOT NULL,
+  `c_d_id` INT(2) NOT NULL,
+  `c_w_id` INT(6) NOT NULL,
+  `c_first` VARCHAR(16) BINARY NULL,
+  `c_middle` CHAR(2) BINARY NULL,
+  `c_last` VARCHAR(16) BINARY NULL,
+  `c_street_1` VARCHAR(20) BINARY NULL,
+  `c_street_2` VARCHAR(20) BINARY NULL,
+  `c_city` VARCHAR(20) BINARY NULL,
+  `c_state` CHAR(2) BINARY NULL,
+  `c_zip` CHAR(9) BINARY NULL,
+  `c_phone` CHAR(16) BINARY NULL,
+  `c_since` DATETIME NULL,
+  `c_credit` CHAR(2) BINARY NULL,
+  `c_credit_lim` DECIMAL(12, 2) NULL,
+  `c_discount` DECIMAL(4, 4) NULL,
+  `c_balance` DECIMAL(12, 2) NULL,
+  `c_ytd_payment` DECIMAL(12, 2) NULL,
+  `c_payment_cnt` INT(8) NULL,
+  `c_delivery_cnt` INT(8) NULL,
+  `c_data` VARCHAR(500) BINARY NULL,
+PRIMARY KEY (`c_w_id`,`c_d_id`,`c_id`)
+)
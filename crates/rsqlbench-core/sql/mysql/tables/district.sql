@@ -0,0 +1,14 @@
+CREATE TABLE `district` (
+  `d_id` INT(2) NOT NULL,
+  `d_w_id` INT(6) NOT NULL,
+  `d_ytd` DECIMAL(12, 2) NULL,
+  `d_tax` DECIMAL(4, 4) NULL,
+  `d_next_o_id` INT NULL,
+  `d_name` VARCHAR(10) BINARY NULL,
+  `d_street_1` VARCHAR(20) BINARY NULL,
+  `d_street_2` VARCHAR(20) BINARY NULL,
+  `d_city` VARCHAR(20) BINARY NULL,
+  `d_state` CHAR(2) BINARY NULL,
+  `d_zip` CHAR(9) BINARY NULL,
+PRIMARY KEY (`d_w_id`,`d_id`)
+)
@@ -0,0 +1,10 @@
+CREATE TABLE `history` (
+  `h_c_id` INT NULL,
+  `h_c_d_id` INT NULL,
+  `h_c_w_id` INT NULL,
+  `h_d_id` INT NULL,
+  `h_w_id` INT NULL,
+  `h_date` DATETIME NULL,
+  `h_amount` DECIMAL(6, 2) NULL,
+  `h_data` VARCHAR(24) BINARY NULL
+)
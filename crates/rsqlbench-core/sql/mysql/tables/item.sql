@@ -0,0 +1,8 @@
+CREATE TABLE `item` (
+  `i_id` INT(6) NOT NULL,
+  `i_im_id` INT NULL,
+  `i_name` VARCHAR(24) BINARY NULL,
+  `i_price` DECIMAL(5, 2) NULL,
+  `i_data` VARCHAR(50) BINARY NULL,
+PRIMARY KEY (`i_id`)
+)
@@ -0,0 +1,11 @@
+CREATE TABLE `oorder` (
+  `o_id` INT NOT NULL,
+  `o_w_id` INT NOT NULL,
+  `o_d_id` INT NOT NULL,
+  `o_c_id` INT NULL,
+  `o_carrier_id` INT NULL,
+  `o_ol_cnt` INT NULL,
+  `o_all_local` INT NULL,
+  `o_entry_d` DATETIME NULL,
+PRIMARY KEY (`o_w_id`,`o_d_id`,`o_id`)
+)
@@ -0,0 +1,13 @@
+CREATE TABLE `order_line` (
+  `ol_w_id` INT NOT NULL,
+  `ol_d_id` INT NOT NULL,
+  `ol_o_id` iNT NOT NULL,
+  `ol_number` INT NOT NULL,
+  `ol_i_id` INT NULL,
+  `ol_delivery_d` DATETIME NULL,
+  `ol_amount` INT NULL,
+  `ol_supply_w_id` INT NULL,
+  `ol_quantity` INT NULL,
+  `ol_dist_info` CHAR(24) BINARY NULL,
+PRIMARY KEY (`ol_w_id`,`ol_d_id`,`ol_o_id`,`ol_number`)
+)
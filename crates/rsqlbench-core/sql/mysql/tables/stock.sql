@@ -0,0 +1,20 @@
+CREATE TABLE `stock` (
+  `s_i_id` INT(6) NOT NULL,
+  `s_w_id` INT(6) NOT NULL,
+  `s_quantity` INT(6) NULL,
+  `s_dist_01` CHAR(24) BINARY NULL,
+  `s_dist_02` CHAR(24) BINARY NULL,
+  `s_dist_03` CHAR(24) BINARY NULL,
+  `s_dist_04` CHAR(24) BINARY NULL,
+  `s_dist_05` CHAR(24) BINARY NULL,
+  `s_dist_06` CHAR(24) BINARY NULL,
+  `s_dist_07` CHAR(24) BINARY NULL,
+  `s_dist_08` CHAR(24) BINARY NULL,
+  `s_dist_09` CHAR(24) BINARY NULL,
+  `s_dist_10` CHAR(24) BINARY NULL,
+  `s_ytd` BIGINT(10) NULL,
+  `s_order_cnt` INT(6) NULL,
+  `s_remote_cnt` INT(6) NULL,
+  `s_data` VARCHAR(50) BINARY NULL,
+PRIMARY KEY (`s_w_id`,`s_i_id`)
+)
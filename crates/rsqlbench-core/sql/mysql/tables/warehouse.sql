@@ -0,0 +1,12 @@
+CREATE TABLE `warehouse` (
+  `w_id` INT(6) NOT NULL,
+  `w_ytd` DECIMAL(12, 2) NULL,
+  `w_tax` DECIMAL(4, 4) NULL,
+  `w_name` VARCHAR(10) BINARY NULL,
+  `w_street_1` VARCHAR(20) BINARY NULL,
+  `w_street_2` VARCHAR(20) BINARY NULL,
+  `w_city` VARCHAR(20) BINARY NULL,
+  `w_state` CHAR(2) BINARY NULL,
+  `w_zip` CHAR(9) BINARY NULL,
+PRIMARY KEY (`w_id`)
+)
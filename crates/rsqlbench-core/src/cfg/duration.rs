@@ -0,0 +1,128 @@
+//! Deserialize human readable durations such as `90s`, `2m30s` or `1h`.
+//!
+//! Plain numbers are still accepted for compatibility and interpreted in unit specified by
+//! deserializer, e.g. `ramp_up: 5` means 5 minutes.
+
+use std::{fmt, time::Duration};
+
+use serde::{de, Deserialize, Deserializer};
+
+struct DurationVisitor {
+    unit: Duration,
+}
+
+impl DurationVisitor {
+    fn scale<E: de::Error>(&self, v: f64) -> Result<Duration, E> {
+        Duration::try_from_secs_f64(self.unit.as_secs_f64() * v)
+            .map_err(|e| E::custom(format!("invalid duration {v}: {e}")))
+    }
+}
+
+impl<'de> de::Visitor<'de> for DurationVisitor {
+    type Value = Duration;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "a duration such as \"90s\", \"2m30s\", \"1h\" or a number of {}s",
+            if self.unit.as_secs() == 60 {
+                "minute"
+            } else {
+                "second"
+            }
+        )
+    }
+
+    fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+        self.scale(v as f64)
+    }
+
+    fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+        self.scale(v as f64)
+    }
+
+    fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+        self.scale(v)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        let v = v.trim();
+        // Values from environment variables are always strings.
+        if let Ok(number) = v.parse::<f64>() {
+            return self.scale(number);
+        }
+        humantime::parse_duration(v).map_err(|e| E::custom(format!("invalid duration {v:?}: {e}")))
+    }
+}
+
+/// Plain numbers are treated as minutes.
+pub fn minutes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    deserializer.deserialize_any(DurationVisitor {
+        unit: Duration::from_secs(60),
+    })
+}
+
+/// Plain numbers are treated as seconds.
+pub fn seconds<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+    deserializer.deserialize_any(DurationVisitor {
+        unit: Duration::from_secs(1),
+    })
+}
+
+/// Optional duration, plain numbers are treated as seconds.
+pub fn option_seconds<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Duration>, D::Error> {
+    #[derive(Deserialize)]
+    struct Seconds(#[serde(deserialize_with = "seconds")] Duration);
+
+    Ok(Option::<Seconds>::deserialize(deserializer)?.map(|s| s.0))
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use serde::Deserialize;
+
+    #[derive(Debug, Deserialize)]
+    struct Durations {
+        #[serde(deserialize_with = "super::minutes")]
+        minutes: Duration,
+        #[serde(deserialize_with = "super::seconds")]
+        seconds: Duration,
+    }
+
+    fn parse(minutes: &str, seconds: &str) -> Result<Durations, serde::de::value::Error> {
+        use serde::de::value::MapDeserializer;
+        Durations::deserialize(MapDeserializer::new(
+            [("minutes", minutes), ("seconds", seconds)].into_iter(),
+        ))
+    }
+
+    #[test]
+    fn human() {
+        let d = parse("2m30s", "1h").unwrap();
+        assert_eq!(d.minutes, Duration::from_secs(150));
+        assert_eq!(d.seconds, Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn plain_number() {
+        let d = parse("5", "10").unwrap();
+        assert_eq!(d.minutes, Duration::from_secs(300));
+        assert_eq!(d.seconds, Duration::from_secs(10));
+
+        let d = parse("0.5", "90s").unwrap();
+        assert_eq!(d.minutes, Duration::from_secs(30));
+        assert_eq!(d.seconds, Duration::from_secs(90));
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(parse("-1", "1s").is_err());
+        assert!(parse("1e30", "1s").is_err());
+        assert!(parse("5", "inf").is_err());
+        assert!(parse("5 apples", "1s").is_err());
+    }
+}
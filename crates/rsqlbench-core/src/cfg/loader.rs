@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use crate::tpcc::model::ScaleModel;
+
 #[derive(Debug, Deserialize)]
 pub struct Loader {
     /// Parallelism for loading data.
@@ -7,4 +9,64 @@ pub struct Loader {
 
     /// Count of warehouses.
     pub warehouse: u32,
+
+    /// Cardinalities of populated tables, `spec`(default), `mini` or customized values.
+    #[serde(default)]
+    pub scale: ScaleModel,
+
+    /// How to insert rows.
+    #[serde(default)]
+    pub method: LoadMethod,
+
+    /// Rows bound to every execution of `prepared` method, only for YashanDB now.
+    #[serde(default = "default_batch_size")]
+    pub batch_size: usize,
+
+    /// Operations after data loaded, only for MySQL now.
+    #[serde(default)]
+    pub after_loaded: AfterLoaded,
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct AfterLoaded {
+    /// Build secondary indexes(such as customer last name index) after loading rather than with tables.
+    #[serde(default = "default_true")]
+    pub indexes: bool,
+
+    /// Build foreign keys between tables.
+    #[serde(default)]
+    pub foreign_keys: bool,
+
+    /// Collect statistics of tables.
+    #[serde(default)]
+    pub analyze: bool,
+}
+
+impl Default for AfterLoaded {
+    fn default() -> Self {
+        Self {
+            indexes: true,
+            foreign_keys: false,
+            analyze: false,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_batch_size() -> usize {
+    1000
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoadMethod {
+    /// Values are formatted into SQL text.
+    #[default]
+    Direct,
+
+    /// Values are bound to server side prepared statements.
+    Prepared,
 }
@@ -1,11 +1,15 @@
 mod benchmark;
 mod connection;
+pub mod duration;
 mod loader;
+mod schema;
+pub mod secret;
 pub mod tpcc;
 
 pub use benchmark::*;
 pub use connection::*;
 pub use loader::*;
+pub use schema::*;
 
 use serde::Deserialize;
 
@@ -14,4 +18,8 @@ pub struct BenchConfig {
     pub loader: Loader,
     pub connection: Connection,
     pub benchmark: Benchmark,
+
+    /// Variants of tables, such as partitioning and storage engine.
+    #[serde(default)]
+    pub schema: Schema,
 }
@@ -0,0 +1,184 @@
+use std::path::PathBuf;
+
+use anyhow::anyhow;
+use serde::Deserialize;
+
+/// Variants of tables created by SUTs when building schema.
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct Schema {
+    /// Partitioning of tables keyed by warehouse(all tables except `item`).
+    pub partition: Option<Partition>,
+
+    /// Storage engine of tables, such as `InnoDB`, `MyRocks`. Only for MySQL.
+    pub engine: Option<String>,
+
+    /// Options appended to every `CREATE TABLE`, such as `ROW_FORMAT=COMPRESSED KEY_BLOCK_SIZE=8` for MySQL
+    /// or `COMPRESS` for YashanDB.
+    pub table_options: Option<String>,
+
+    /// Tablespace where tables are created. Only for YashanDB.
+    pub tablespace: Option<String>,
+
+    /// Organization of tables. Only for YashanDB.
+    pub organization: Option<Organization>,
+
+    /// Directory of SQL files overriding built-in ones, such as `tables/stock.sql`, `indexes.sql` and
+    /// `new_order.sql`.
+    ///
+    /// Variables `{{database}}`, `{{warehouses}}`, `{{partitions}}` and `{{items}}` in files are replaced.
+    pub sql_dir: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum Partition {
+    /// `PARTITION BY HASH(<warehouse id>) PARTITIONS <partitions>`.
+    Hash { partitions: u32 },
+
+    /// `PARTITION BY RANGE(<warehouse id>)`, every partition holds `warehouses` warehouses.
+    Range { warehouses: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Organization {
+    Heap,
+    Lsc,
+}
+
+impl Partition {
+    /// Partition clause of table with warehouse id stored in `column`.
+    pub fn clause(&self, column: &str, warehouse_count: u32) -> String {
+        match *self {
+            Partition::Hash { partitions } => {
+                format!("PARTITION BY HASH ({column}) PARTITIONS {partitions}")
+            }
+            Partition::Range { warehouses } => {
+                let warehouses = warehouses.max(1);
+                let partitions = (1..=warehouse_count)
+                    .step_by(warehouses as usize)
+                    .enumerate()
+                    .map(|(i, first)| {
+                        let bound = (first + warehouses).min(warehouse_count + 1);
+                        format!("PARTITION p{} VALUES LESS THAN ({bound})", i + 1)
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("PARTITION BY RANGE ({column}) ({partitions})")
+            }
+        }
+    }
+}
+
+impl Schema {
+    /// Partitions of zero hash partitions or zero warehouses would be rejected by database or never end.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        match self.partition {
+            Some(Partition::Hash { partitions: 0 }) => Err(anyhow!(
+                "`schema.partition.partitions` must be greater than zero"
+            )),
+            Some(Partition::Range { warehouses: 0 }) => Err(anyhow!(
+                "`schema.partition.warehouses` must be greater than zero"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Count of partitions of every partitioned table, zero if not partitioned.
+    pub fn partitions(&self, warehouse_count: u32) -> u32 {
+        match self.partition {
+            None => 0,
+            Some(Partition::Hash { partitions }) => partitions,
+            Some(Partition::Range { warehouses }) => warehouse_count.div_ceil(warehouses.max(1)),
+        }
+    }
+
+    /// Clauses following columns definition of MySQL `CREATE TABLE`.
+    pub fn mysql_suffix(&self, warehouse_column: Option<&str>, warehouse_count: u32) -> String {
+        let mut clauses = vec![];
+        if let Some(engine) = &self.engine {
+            clauses.push(format!("ENGINE={engine}"));
+        }
+        clauses.extend(self.table_options.clone());
+        if let (Some(partition), Some(column)) = (&self.partition, warehouse_column) {
+            clauses.push(partition.clause(column, warehouse_count));
+        }
+        clauses.join(" ")
+    }
+
+    /// Clauses following columns definition of YashanDB `CREATE TABLE`.
+    pub fn yasdb_suffix(&self, warehouse_column: Option<&str>, warehouse_count: u32) -> String {
+        let mut clauses = vec![];
+        if let Some(organization) = self.organization {
+            clauses.push(
+                match organization {
+                    Organization::Heap => "ORGANIZATION HEAP",
+                    Organization::Lsc => "ORGANIZATION LSC",
+                }
+                .to_string(),
+            );
+        }
+        if let Some(tablespace) = &self.tablespace {
+            clauses.push(format!("TABLESPACE {tablespace}"));
+        }
+        clauses.extend(self.table_options.clone());
+        if let (Some(partition), Some(column)) = (&self.partition, warehouse_column) {
+            clauses.push(partition.clause(column, warehouse_count));
+        }
+        clauses.join(" ")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{Partition, Schema};
+
+    #[test]
+    fn range_partition() {
+        assert_eq!(
+            Partition::Range { warehouses: 4 }.clause("w_id", 10),
+            "PARTITION BY RANGE (w_id) (PARTITION p1 VALUES LESS THAN (5), PARTITION p2 VALUES LESS THAN (9), PARTITION p3 VALUES LESS THAN (11))"
+        );
+        let schema = Schema {
+            partition: Some(Partition::Range { warehouses: 4 }),
+            ..Default::default()
+        };
+        assert_eq!(schema.partitions(10), 3);
+    }
+
+    #[test]
+    fn verify() {
+        for (partition, valid) in [
+            (None, true),
+            (Some(Partition::Hash { partitions: 8 }), true),
+            (Some(Partition::Hash { partitions: 0 }), false),
+            (Some(Partition::Range { warehouses: 4 }), true),
+            (Some(Partition::Range { warehouses: 0 }), false),
+        ] {
+            let schema = Schema {
+                partition,
+                ..Default::default()
+            };
+            assert_eq!(schema.verify().is_ok(), valid, "{partition:?}");
+        }
+    }
+
+    #[test]
+    fn mysql_suffix() {
+        let schema = Schema {
+            partition: Some(Partition::Hash { partitions: 8 }),
+            engine: Some("InnoDB".into()),
+            table_options: Some("ROW_FORMAT=COMPRESSED".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            schema.mysql_suffix(Some("s_w_id"), 10),
+            "ENGINE=InnoDB ROW_FORMAT=COMPRESSED PARTITION BY HASH (s_w_id) PARTITIONS 8"
+        );
+        assert_eq!(
+            schema.mysql_suffix(None, 10),
+            "ENGINE=InnoDB ROW_FORMAT=COMPRESSED"
+        );
+        assert_eq!(Schema::default().mysql_suffix(Some("w_id"), 10), "");
+    }
+}
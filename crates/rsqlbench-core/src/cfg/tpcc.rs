@@ -1,18 +1,31 @@
+use std::{fmt, str::FromStr, time::Duration};
+
 use serde::Deserialize;
 
-#[derive(Debug, Deserialize)]
+use super::duration;
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct TpccBenchmark {
     /// Simulating keying and thinking time. Disable this feature for more parallelism with less terminals.
     #[serde(default)]
     pub keying_and_thinking: bool,
 
-    /// Ramp up duration(minutes).
-    pub ramp_up: usize,
+    /// Ramp up duration, such as `90s`, `2m30s`(plain number for minutes).
+    #[serde(deserialize_with = "duration::minutes")]
+    pub ramp_up: Duration,
 
-    /// Benchmark duration(minutes).
+    /// Benchmark duration, such as `10m`, `1h`(plain number for minutes).
     ///
-    /// Benchmark will finish after specified minutes.
-    pub baking: usize,
+    /// Benchmark will finish after specified duration.
+    #[serde(deserialize_with = "duration::minutes")]
+    pub baking: Duration,
+
+    /// Interval for reporting progress(plain number for seconds).
+    #[serde(
+        default = "default_report_interval",
+        deserialize_with = "duration::seconds"
+    )]
+    pub report_interval: Duration,
 
     /// Terminals to simulate.
     pub terminals: usize,
@@ -22,6 +35,64 @@ pub struct TpccBenchmark {
     /// Unnecessary to specify weight of new order which will be determined by other weights
     /// with assuming that all weights sum to 100.0%.
     pub transactions: TpccTransaction,
+
+    /// Isolation level of every terminal connection, default to the one of database.
+    #[serde(default)]
+    pub isolation: Option<IsolationLevel>,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationLevel {
+    ReadUncommitted,
+    ReadCommitted,
+    RepeatableRead,
+    Serializable,
+}
+
+impl IsolationLevel {
+    pub const ALL: [IsolationLevel; 4] = [
+        IsolationLevel::ReadUncommitted,
+        IsolationLevel::ReadCommitted,
+        IsolationLevel::RepeatableRead,
+        IsolationLevel::Serializable,
+    ];
+
+    /// Name in SQL, such as `READ COMMITTED`.
+    pub fn sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+impl fmt::Display for IsolationLevel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            IsolationLevel::ReadUncommitted => "read_uncommitted",
+            IsolationLevel::ReadCommitted => "read_committed",
+            IsolationLevel::RepeatableRead => "repeatable_read",
+            IsolationLevel::Serializable => "serializable",
+        })
+    }
+}
+
+impl FromStr for IsolationLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.to_string() == s.to_lowercase().replace(['-', ' '], "_"))
+            .ok_or_else(|| format!("Unknown isolation level: {s}"))
+    }
+}
+
+fn default_report_interval() -> Duration {
+    Duration::from_secs(10)
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -78,3 +149,22 @@ impl TpccTransaction {
         100.0 - self.payment - self.order_status - self.delivery - self.stock_level
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::IsolationLevel;
+
+    #[test]
+    fn isolation_level() {
+        assert_eq!(
+            "read_committed".parse::<IsolationLevel>().unwrap(),
+            IsolationLevel::ReadCommitted
+        );
+        assert_eq!(
+            "REPEATABLE-READ".parse::<IsolationLevel>().unwrap(),
+            IsolationLevel::RepeatableRead
+        );
+        assert!("snapshot".parse::<IsolationLevel>().is_err());
+        assert_eq!(IsolationLevel::Serializable.sql(), "SERIALIZABLE");
+    }
+}
@@ -0,0 +1,86 @@
+use std::ops::RangeInclusive;
+
+use super::model::{ScaleModel, DISTRICT_PER_WAREHOUSE};
+
+#[derive(Debug, thiserror::Error)]
+pub enum CheckError {
+    #[error("{0} is not supported by this SUT")]
+    Unsupported(&'static str),
+
+    #[error("Table {table} has {actual} rows, expected {expected:?}")]
+    Cardinality {
+        table: &'static str,
+        actual: u64,
+        expected: RangeInclusive<u64>,
+    },
+
+    #[error("Consistency condition {condition} is violated by {violations} rows")]
+    Consistency {
+        condition: &'static str,
+        violations: u64,
+    },
+}
+
+/// Rows of every table just after loaded(4.3.3.1), order lines vary since every order has 5 to 15 lines.
+pub fn expected_rows(
+    warehouses: u32,
+    scale: &ScaleModel,
+) -> [(&'static str, RangeInclusive<u64>); 9] {
+    let warehouses = warehouses as u64;
+    let districts = warehouses * DISTRICT_PER_WAREHOUSE as u64;
+    let customers = districts * scale.customers_per_district as u64;
+    let orders = districts * scale.orders_per_district as u64;
+    let new_orders = districts * scale.new_orders_per_district() as u64;
+    let exact = |n| n..=n;
+    [
+        ("warehouse", exact(warehouses)),
+        ("district", exact(districts)),
+        ("customer", exact(customers)),
+        ("history", exact(customers)),
+        ("oorder", exact(orders)),
+        ("new_order", exact(new_orders)),
+        ("order_line", orders * 5..=orders * 15),
+        ("item", exact(scale.items as u64)),
+        (
+            "stock",
+            exact(warehouses * scale.stocks_per_warehouse() as u64),
+        ),
+    ]
+}
+
+/// Consistency conditions of 3.3.2.1 to 3.3.2.4, every query counts rows violating the condition.
+pub const CONSISTENCY_CONDITIONS: [(&str, &str); 4] = [
+    (
+        "3.3.2.1 W_YTD = sum(D_YTD)",
+        "SELECT COUNT(*) FROM warehouse WHERE w_ytd <> (SELECT SUM(d_ytd) FROM district WHERE d_w_id = w_id)",
+    ),
+    (
+        "3.3.2.2 D_NEXT_O_ID - 1 = max(O_ID) = max(NO_O_ID)",
+        "SELECT COUNT(*) FROM district WHERE d_next_o_id - 1 <> (SELECT MAX(o_id) FROM oorder WHERE o_w_id = d_w_id AND o_d_id = d_id) OR d_next_o_id - 1 <> (SELECT MAX(no_o_id) FROM new_order WHERE no_w_id = d_w_id AND no_d_id = d_id)",
+    ),
+    (
+        "3.3.2.3 max(NO_O_ID) - min(NO_O_ID) + 1 = count(NEW_ORDER)",
+        "SELECT COUNT(*) FROM (SELECT no_w_id, no_d_id FROM new_order GROUP BY no_w_id, no_d_id HAVING MAX(no_o_id) - MIN(no_o_id) + 1 <> COUNT(*)) v",
+    ),
+    (
+        "3.3.2.4 sum(O_OL_CNT) = count(ORDER_LINE)",
+        "SELECT COUNT(*) FROM district WHERE (SELECT SUM(o_ol_cnt) FROM oorder WHERE o_w_id = d_w_id AND o_d_id = d_id) <> (SELECT COUNT(*) FROM order_line WHERE ol_w_id = d_w_id AND ol_d_id = d_id)",
+    ),
+];
+
+#[cfg(test)]
+mod test {
+    use crate::tpcc::model::ScaleModel;
+
+    use super::expected_rows;
+
+    #[test]
+    fn spec_rows() {
+        let rows = expected_rows(2, &ScaleModel::SPEC);
+        let get = |table| rows.iter().find(|(t, _)| *t == table).unwrap().1.clone();
+        assert_eq!(get("customer"), 60000..=60000);
+        assert_eq!(get("new_order"), 18000..=18000);
+        assert_eq!(get("stock"), 200000..=200000);
+        assert_eq!(get("order_line"), 300000..=900000);
+    }
+}
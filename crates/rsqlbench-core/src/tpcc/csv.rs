@@ -0,0 +1,384 @@
+//! Write populated tables as CSV files, which could be ingested by fast-load commands of SUTs.
+//!
+//! Fields are separated by `,` and rows by `\n`, `NULL` is written as `\N`. Generated strings are alphanumeric, so no
+//! quoting is needed. Files are named as `<table>.csv`, or `<table>.<warehouse id>.csv` when split per warehouse.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use time::OffsetDateTime;
+
+use super::model::{
+    Customer, CustomerGenerator, District, DistrictGenerator, History, HistoryGenerator, Item,
+    ItemGenerator, NewOrder, Order, OrderGenerator, OrderLine, OrderLineGenerator, ScaleModel,
+    Stock, StockGenerator, Warehouse,
+};
+
+const DATE_TIME_FORMAT: &[time::format_description::FormatItem] =
+    time::macros::format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+
+/// Tables and their columns in order of CSV fields.
+pub const TABLES: [(&str, &str); 9] = [
+    ("item", "i_id, i_im_id, i_name, i_price, i_data"),
+    ("warehouse", "w_id, w_name, w_street_1, w_street_2, w_city, w_state, w_zip, w_tax, w_ytd"),
+    ("stock", "s_i_id, s_w_id, s_quantity, s_dist_01, s_dist_02, s_dist_03, s_dist_04, s_dist_05, s_dist_06, s_dist_07, s_dist_08, s_dist_09, s_dist_10, s_ytd, s_order_cnt, s_remote_cnt, s_data"),
+    ("district", "d_id, d_w_id, d_name, d_street_1, d_street_2, d_city, d_state, d_zip, d_tax, d_ytd, d_next_o_id"),
+    ("customer", "c_id, c_d_id, c_w_id, c_first, c_middle, c_last, c_street_1, c_street_2, c_city, c_state, c_zip, c_phone, c_since, c_credit, c_credit_lim, c_discount, c_balance, c_ytd_payment, c_payment_cnt, c_delivery_cnt, c_data"),
+    ("history", "h_c_id, h_c_d_id, h_c_w_id, h_d_id, h_w_id, h_date, h_amount, h_data"),
+    ("oorder", "o_id, o_d_id, o_w_id, o_c_id, o_entry_d, o_carrier_id, o_ol_cnt, o_all_local"),
+    ("new_order", "no_o_id, no_d_id, no_w_id"),
+    ("order_line", "ol_o_id, ol_d_id, ol_w_id, ol_number, ol_i_id, ol_supply_w_id, ol_delivery_d, ol_quantity, ol_amount, ol_dist_info"),
+];
+
+/// Columns of table in order of CSV fields.
+pub fn columns(table: &str) -> Option<&'static str> {
+    TABLES
+        .iter()
+        .find(|(name, _)| *name == table)
+        .map(|(_, columns)| *columns)
+}
+
+/// Table of a CSV file according to its name.
+pub fn table_of(path: &Path) -> Option<&'static str> {
+    let file_name = path.file_name()?.to_str()?;
+    if !file_name.ends_with(".csv") {
+        return None;
+    }
+    let table = file_name.split('.').next()?;
+    TABLES
+        .iter()
+        .find(|(name, _)| *name == table)
+        .map(|(name, _)| *name)
+}
+
+pub trait CsvRow {
+    const TABLE: &'static str;
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()>;
+}
+
+struct Null<'a, T>(&'a Option<T>);
+
+impl<'a, T: Display> Display for Null<'a, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(v) => v.fmt(f),
+            None => f.write_str("\\N"),
+        }
+    }
+}
+
+struct DateTime(Option<OffsetDateTime>);
+
+impl Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0
+            .unwrap_or_else(OffsetDateTime::now_utc)
+            .format(DATE_TIME_FORMAT)
+            .map_err(|_| std::fmt::Error)?
+            .fmt(f)
+    }
+}
+
+impl CsvRow for Item {
+    const TABLE: &'static str = "item";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            id,
+            image_id,
+            name,
+            price,
+            data,
+        } = self;
+        writeln!(w, "{id},{image_id},{name},{price},{data}")
+    }
+}
+
+impl CsvRow for Warehouse {
+    const TABLE: &'static str = "warehouse";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            id,
+            name,
+            street: (street0, street1),
+            city,
+            state,
+            zip,
+            tax,
+            ytd,
+        } = self;
+        writeln!(
+            w,
+            "{id},{name},{street0},{street1},{city},{state},{zip},{tax},{ytd}"
+        )
+    }
+}
+
+impl CsvRow for Stock {
+    const TABLE: &'static str = "stock";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            item_id,
+            warehouse_id,
+            quantity,
+            dist,
+            ytd,
+            order_count,
+            remote_count,
+            data,
+        } = self;
+        write!(w, "{item_id},{warehouse_id},{quantity},")?;
+        for dist in dist {
+            write!(w, "{dist},")?;
+        }
+        writeln!(w, "{ytd},{order_count},{remote_count},{data}")
+    }
+}
+
+impl CsvRow for District {
+    const TABLE: &'static str = "district";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            id,
+            warehouse_id,
+            name,
+            street: (street0, street1),
+            city,
+            state,
+            zip,
+            tax,
+            ytd,
+            next_order_id,
+        } = self;
+        writeln!(w, "{id},{warehouse_id},{name},{street0},{street1},{city},{state},{zip},{tax},{ytd},{next_order_id}")
+    }
+}
+
+impl CsvRow for Customer {
+    const TABLE: &'static str = "customer";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            id,
+            district_id,
+            warehouse_id,
+            first_name,
+            middle_name,
+            last_name,
+            street: (street0, street1),
+            city,
+            state,
+            zip,
+            phone,
+            since,
+            credit,
+            credit_limit,
+            discount,
+            balance,
+            ytd_payment,
+            payment_count,
+            delivery_count,
+            data,
+        } = self;
+        let since = DateTime(*since);
+        writeln!(w, "{id},{district_id},{warehouse_id},{first_name},{middle_name},{last_name},{street0},{street1},{city},{state},{zip},{phone},{since},{credit},{credit_limit},{discount},{balance},{ytd_payment},{payment_count},{delivery_count},{data}")
+    }
+}
+
+impl CsvRow for History {
+    const TABLE: &'static str = "history";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            customer_id,
+            customer_district_id,
+            customer_warehouse_id,
+            district_id,
+            warehouse_id,
+            date,
+            amount,
+            data,
+        } = self;
+        let date = DateTime(*date);
+        writeln!(w, "{customer_id},{customer_district_id},{customer_warehouse_id},{district_id},{warehouse_id},{date},{amount},{data}")
+    }
+}
+
+impl CsvRow for Order {
+    const TABLE: &'static str = "oorder";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            id,
+            district_id,
+            warehouse_id,
+            customer_id,
+            entry_date,
+            carrier_id,
+            order_lines_count,
+            all_local,
+        } = self;
+        let entry_date = DateTime(*entry_date);
+        let carrier_id = Null(carrier_id);
+        let all_local = *all_local as u8;
+        writeln!(w, "{id},{district_id},{warehouse_id},{customer_id},{entry_date},{carrier_id},{order_lines_count},{all_local}")
+    }
+}
+
+impl CsvRow for NewOrder {
+    const TABLE: &'static str = "new_order";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            order_id,
+            district_id,
+            warehouse_id,
+        } = self;
+        writeln!(w, "{order_id},{district_id},{warehouse_id}")
+    }
+}
+
+impl CsvRow for OrderLine {
+    const TABLE: &'static str = "order_line";
+
+    fn write(&self, w: &mut impl Write) -> io::Result<()> {
+        let Self {
+            order_id,
+            district_id,
+            warehouse_id,
+            number,
+            item_id,
+            supply_warehouse_id,
+            delivery_date,
+            quantity,
+            amount,
+            dist_info,
+        } = self;
+        let delivery_date = Null(&delivery_date.map(|d| DateTime(Some(d))));
+        writeln!(w, "{order_id},{district_id},{warehouse_id},{number},{item_id},{supply_warehouse_id},{delivery_date},{quantity},{amount},{dist_info}")
+    }
+}
+
+/// Writers of tables, files are created on first row.
+pub struct CsvWriter {
+    dir: PathBuf,
+    warehouse_id: Option<u32>,
+    files: HashMap<&'static str, BufWriter<File>>,
+}
+
+impl CsvWriter {
+    /// Write into `<table>.csv`, or `<table>.<warehouse_id>.csv` if `warehouse_id` is specified.
+    pub fn new(dir: &Path, warehouse_id: Option<u32>) -> Self {
+        Self {
+            dir: dir.to_path_buf(),
+            warehouse_id,
+            files: HashMap::new(),
+        }
+    }
+
+    pub fn write<R: CsvRow>(&mut self, row: &R) -> io::Result<()> {
+        if !self.files.contains_key(R::TABLE) {
+            let file_name = match self.warehouse_id {
+                Some(id) => format!("{}.{id}.csv", R::TABLE),
+                None => format!("{}.csv", R::TABLE),
+            };
+            let file = File::create(self.dir.join(file_name))?;
+            self.files.insert(R::TABLE, BufWriter::new(file));
+        }
+        row.write(self.files.get_mut(R::TABLE).unwrap())
+    }
+
+    pub fn finish(self) -> io::Result<()> {
+        for (_, mut file) in self.files {
+            file.flush()?;
+        }
+        Ok(())
+    }
+}
+
+pub fn write_items(writer: &mut CsvWriter, scale: &ScaleModel) -> io::Result<()> {
+    for item in ItemGenerator::new(1..=(scale.items as _)) {
+        writer.write(&item)?;
+    }
+    Ok(())
+}
+
+/// Write warehouse and all rows belonging to it.
+pub fn write_warehouse(
+    writer: &mut CsvWriter,
+    warehouse: &Warehouse,
+    scale: &ScaleModel,
+) -> io::Result<()> {
+    writer.write(warehouse)?;
+    for stock in StockGenerator::from_warehouse(warehouse, scale) {
+        writer.write(&stock)?;
+    }
+    for district in DistrictGenerator::from_warehouse(warehouse, scale) {
+        writer.write(&district)?;
+        for customer in CustomerGenerator::from_district(&district, scale) {
+            writer.write(&customer)?;
+            for history in HistoryGenerator::from_customer(&customer) {
+                writer.write(&history)?;
+            }
+        }
+        for (order, new_order) in OrderGenerator::from_district(&district, scale) {
+            writer.write(&order)?;
+            if let Some(new_order) = new_order {
+                writer.write(&new_order)?;
+            }
+            for order_line in OrderLineGenerator::from_order(&order, scale) {
+                writer.write(&order_line)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use crate::tpcc::model::{ScaleModel, WarehouseGenerator};
+
+    use super::{columns, table_of, write_warehouse, CsvWriter};
+
+    #[test]
+    fn table_files() {
+        assert_eq!(table_of(Path::new("/tmp/stock.3.csv")), Some("stock"));
+        assert_eq!(table_of(Path::new("order_line.csv")), Some("order_line"));
+        assert_eq!(table_of(Path::new("orders.csv")), None);
+        assert_eq!(table_of(Path::new("stock.txt")), None);
+    }
+
+    #[test]
+    fn fields_match_columns() {
+        let dir = std::env::temp_dir().join(format!("rsqlbench-csv-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let scale = ScaleModel::MINI;
+        let mut writer = CsvWriter::new(&dir, Some(1));
+        for warehouse in WarehouseGenerator::new(1..=1) {
+            write_warehouse(&mut writer, &warehouse, &scale).unwrap();
+        }
+        writer.finish().unwrap();
+
+        for entry in std::fs::read_dir(&dir).unwrap() {
+            let path = entry.unwrap().path();
+            let table = table_of(&path).unwrap();
+            let fields = columns(table).unwrap().split(", ").count();
+            let content = std::fs::read_to_string(&path).unwrap();
+            assert!(content
+                .lines()
+                .all(|line| line.split(',').count() == fields));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
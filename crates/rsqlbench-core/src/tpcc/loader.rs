@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use super::model::{ItemGenerator, Warehouse};
 
 #[async_trait::async_trait]
@@ -7,4 +9,11 @@ pub trait Loader: Send {
         &mut self,
         generator: async_channel::Receiver<Warehouse>,
     ) -> anyhow::Result<()>;
+
+    /// Load a table from CSV file written by [`super::csv`] with fast-load command of SUT.
+    async fn load_csv(&mut self, table: &str, path: &Path) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!(
+            "Could not load {table} from {path:?}, CSV loading is not supported by this SUT"
+        ))
+    }
 }
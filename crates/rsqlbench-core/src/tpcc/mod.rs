@@ -1,3 +1,5 @@
+pub mod check;
+pub mod csv;
 pub mod loader;
 pub mod model;
 pub mod random;
@@ -3,9 +3,9 @@ use std::ops::RangeInclusive;
 use rand::{thread_rng, Rng};
 use time::OffsetDateTime;
 
-use crate::tpcc::random::{rand_double, rand_last_name, rand_str, rand_zip};
+use crate::tpcc::random::{last_name, rand_double, rand_last_name, rand_str, rand_zip};
 
-use super::{District, CUSTOMER_PER_DISTRICT};
+use super::{District, ScaleModel};
 
 #[derive(Debug)]
 pub struct Customer {
@@ -35,14 +35,16 @@ pub struct CustomerGenerator {
     district_id: u8,
     warehouse_id: u32,
     id_range: RangeInclusive<u16>,
+    scale: ScaleModel,
 }
 
 impl CustomerGenerator {
-    pub fn from_district(district: &District) -> Self {
+    pub fn from_district(district: &District, scale: &ScaleModel) -> Self {
         Self {
             district_id: district.id,
             warehouse_id: district.warehouse_id,
-            id_range: 1..=(CUSTOMER_PER_DISTRICT as _),
+            id_range: 1..=(scale.customers_per_district as _),
+            scale: *scale,
         }
     }
 }
@@ -57,7 +59,12 @@ impl Iterator for CustomerGenerator {
             warehouse_id: self.warehouse_id,
             first_name: rand_str(8, 16),
             middle_name: "OE".to_string(),
-            last_name: rand_last_name(),
+            // 4.3.3.1 C_LAST of the first 1000 customers are generated from C_ID-1.
+            last_name: if id <= 1000 {
+                last_name(id as usize - 1)
+            } else {
+                rand_last_name(&self.scale)
+            },
             street: (rand_str(10, 20), rand_str(10, 20)),
             city: rand_str(10, 20),
             state: rand_str(2, 2),
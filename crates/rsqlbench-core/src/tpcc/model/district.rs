@@ -2,7 +2,7 @@ use std::ops::RangeInclusive;
 
 use crate::tpcc::random::{rand_double, rand_str, rand_zip};
 
-use super::{Warehouse, DISTRICT_PER_WAREHOUSE};
+use super::{ScaleModel, Warehouse, DISTRICT_PER_WAREHOUSE};
 
 #[derive(Debug)]
 pub struct District {
@@ -21,13 +21,15 @@ pub struct District {
 pub struct DistrictGenerator {
     id_range: RangeInclusive<u8>,
     warehouse_id: u32,
+    next_order_id: u32,
 }
 
 impl DistrictGenerator {
-    pub fn from_warehouse(warehouse: &Warehouse) -> Self {
+    pub fn from_warehouse(warehouse: &Warehouse, scale: &ScaleModel) -> Self {
         Self {
             id_range: 1..=(DISTRICT_PER_WAREHOUSE as _),
             warehouse_id: warehouse.id,
+            next_order_id: scale.orders_per_district as u32 + 1,
         }
     }
 }
@@ -46,7 +48,7 @@ impl Iterator for DistrictGenerator {
             zip: rand_zip(),
             tax: rand_double(0.0, 0.2, 0) as _,
             ytd: 30000.00,
-            next_order_id: 3001,
+            next_order_id: self.next_order_id,
         })
     }
 }
@@ -5,7 +5,7 @@ use time::OffsetDateTime;
 
 use crate::tpcc::random::{rand_double, rand_str};
 
-use super::{District, ORDERS_PER_DISTRICT};
+use super::{District, ScaleModel};
 
 #[derive(Debug)]
 pub struct NewOrder {
@@ -44,17 +44,20 @@ pub struct OrderGenerator {
     district_id: u8,
     warehouse_id: u32,
     id_range: Zip<RangeInclusive<u32>, std::vec::IntoIter<u16>>,
+    delivered: u32,
 }
 
 impl OrderGenerator {
-    pub fn from_district(district: &District) -> Self {
+    pub fn from_district(district: &District, scale: &ScaleModel) -> Self {
         let mut rng = thread_rng();
-        let mut customer_id = (1..=(ORDERS_PER_DISTRICT as _)).collect::<Vec<u16>>();
+        let mut customer_id = (1..=(scale.customers_per_district as _)).collect::<Vec<u16>>();
         customer_id.shuffle(&mut rng);
+        customer_id.truncate(scale.orders_per_district);
         Self {
             district_id: district.id,
             warehouse_id: district.warehouse_id,
-            id_range: (1..=(ORDERS_PER_DISTRICT as _)).zip(customer_id),
+            id_range: (1..=(scale.orders_per_district as _)).zip(customer_id),
+            delivered: scale.delivered_orders_per_district() as _,
         }
     }
 }
@@ -70,7 +73,7 @@ impl Iterator for OrderGenerator {
                 warehouse_id: self.warehouse_id,
                 customer_id,
                 entry_date: None,
-                carrier_id: if id < 2101 {
+                carrier_id: if id <= self.delivered {
                     Some(thread_rng().gen_range(1..=10))
                 } else {
                     None
@@ -78,7 +81,7 @@ impl Iterator for OrderGenerator {
                 order_lines_count: thread_rng().gen_range(5..=15),
                 all_local: true,
             };
-            let new_order = if id <= 2100 {
+            let new_order = if id <= self.delivered {
                 None
             } else {
                 Some(NewOrder {
@@ -97,17 +100,21 @@ pub struct OrderLineGenerator {
     district_id: u8,
     warehouse_id: u32,
     entry_date: Option<OffsetDateTime>,
+    delivered: bool,
     id: RangeInclusive<u8>,
+    items: u32,
 }
 
 impl OrderLineGenerator {
-    pub fn from_order(order: &Order) -> Self {
+    pub fn from_order(order: &Order, scale: &ScaleModel) -> Self {
         Self {
             order_id: order.id,
             district_id: order.district_id,
             warehouse_id: order.warehouse_id,
             entry_date: order.entry_date,
+            delivered: order.carrier_id.is_some(),
             id: 1..=order.order_lines_count,
+            items: scale.items as _,
         }
     }
 }
@@ -121,15 +128,15 @@ impl Iterator for OrderLineGenerator {
             district_id: self.district_id,
             warehouse_id: self.warehouse_id,
             number: id,
-            item_id: thread_rng().gen_range(1..=100000),
+            item_id: thread_rng().gen_range(1..=self.items),
             supply_warehouse_id: self.warehouse_id,
-            delivery_date: if self.order_id < 2101 {
-                self.entry_date
+            delivery_date: if self.delivered {
+                Some(self.entry_date.unwrap_or_else(OffsetDateTime::now_utc))
             } else {
                 None
             },
             quantity: 5,
-            amount: if self.order_id < 2101 {
+            amount: if self.delivered {
                 0.0
             } else {
                 rand_double(0.01, 9999.99, -2) as _
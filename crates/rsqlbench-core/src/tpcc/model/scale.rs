@@ -0,0 +1,183 @@
+use serde::Deserialize;
+
+/// Cardinalities of populated tables.
+///
+/// The default follows 1.3 of TPC-C, smaller models are only useful for functional tests since results are not
+/// comparable with standard ones.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "ScaleConfig")]
+pub struct ScaleModel {
+    /// Items to be populated.
+    pub items: usize,
+
+    /// Customers to be populated for one district.
+    pub customers_per_district: usize,
+
+    /// Orders to be populated for one district.
+    pub orders_per_district: usize,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScaleError {
+    #[error("{0} must be greater than zero")]
+    Zero(&'static str),
+
+    #[error("customers_per_district must not be greater than {max}, got {got}")]
+    TooManyCustomers { max: usize, got: usize },
+
+    #[error("orders_per_district({orders}) must not be greater than customers_per_district({customers})")]
+    TooManyOrders { orders: usize, customers: usize },
+}
+
+impl Default for ScaleModel {
+    fn default() -> Self {
+        Self::SPEC
+    }
+}
+
+impl ScaleModel {
+    pub const SPEC: Self = Self {
+        items: 100_000,
+        customers_per_district: 3000,
+        orders_per_district: 3000,
+    };
+
+    /// About one percent of standard model, for fast functional tests.
+    pub const MINI: Self = Self {
+        items: 1000,
+        customers_per_district: 100,
+        orders_per_district: 100,
+    };
+
+    /// Stocks to be populated for one warehouse, every warehouse stocks all items.
+    pub fn stocks_per_warehouse(&self) -> usize {
+        self.items
+    }
+
+    /// Undelivered orders of one district(900 of 3000 in standard model), which are also populated into new order
+    /// table.
+    pub fn new_orders_per_district(&self) -> usize {
+        self.orders_per_district * 3 / 10
+    }
+
+    /// Orders with ID not greater than this are delivered.
+    pub fn delivered_orders_per_district(&self) -> usize {
+        self.orders_per_district - self.new_orders_per_district()
+    }
+
+    pub fn verify(&self) -> Result<(), ScaleError> {
+        if self.items == 0 {
+            return Err(ScaleError::Zero("items"));
+        }
+        if self.customers_per_district == 0 {
+            return Err(ScaleError::Zero("customers_per_district"));
+        }
+        if self.orders_per_district == 0 {
+            return Err(ScaleError::Zero("orders_per_district"));
+        }
+        // Customer ID is stored in u16.
+        if self.customers_per_district > u16::MAX as usize {
+            return Err(ScaleError::TooManyCustomers {
+                max: u16::MAX as usize,
+                got: self.customers_per_district,
+            });
+        }
+        // Every order belongs to a distinct customer.
+        if self.orders_per_district > self.customers_per_district {
+            return Err(ScaleError::TooManyOrders {
+                orders: self.orders_per_district,
+                customers: self.customers_per_district,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum ScalePreset {
+    #[default]
+    Spec,
+    Mini,
+}
+
+impl From<ScalePreset> for ScaleModel {
+    fn from(preset: ScalePreset) -> Self {
+        match preset {
+            ScalePreset::Spec => Self::SPEC,
+            ScalePreset::Mini => Self::MINI,
+        }
+    }
+}
+
+/// `scale: mini` or `scale: { preset: mini, items: 2000 }`.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ScaleConfig {
+    Preset(ScalePreset),
+    Custom {
+        #[serde(default)]
+        preset: ScalePreset,
+        items: Option<usize>,
+        customers_per_district: Option<usize>,
+        orders_per_district: Option<usize>,
+    },
+}
+
+impl TryFrom<ScaleConfig> for ScaleModel {
+    type Error = ScaleError;
+
+    fn try_from(cfg: ScaleConfig) -> Result<Self, Self::Error> {
+        let scale = match cfg {
+            ScaleConfig::Preset(preset) => preset.into(),
+            ScaleConfig::Custom {
+                preset,
+                items,
+                customers_per_district,
+                orders_per_district,
+            } => {
+                let base = ScaleModel::from(preset);
+                Self {
+                    items: items.unwrap_or(base.items),
+                    customers_per_district: customers_per_district
+                        .unwrap_or(base.customers_per_district),
+                    orders_per_district: orders_per_district.unwrap_or(base.orders_per_district),
+                }
+            }
+        };
+        scale.verify()?;
+        Ok(scale)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde::{de::value::MapDeserializer, Deserialize};
+
+    use super::ScaleModel;
+
+    #[test]
+    fn preset() {
+        let mini = ScaleModel::deserialize(serde::de::value::StrDeserializer::<
+            serde::de::value::Error,
+        >::new("mini"))
+        .unwrap();
+        assert_eq!(mini, ScaleModel::MINI);
+        assert_eq!(ScaleModel::SPEC.new_orders_per_district(), 900);
+        assert_eq!(ScaleModel::SPEC.delivered_orders_per_district(), 2100);
+    }
+
+    #[test]
+    fn custom() {
+        let parse = |kv: &[(&'static str, usize)]| {
+            ScaleModel::deserialize(MapDeserializer::<_, serde::de::value::Error>::new(
+                kv.iter().copied(),
+            ))
+        };
+        let scale = parse(&[("items", 5000)]).unwrap();
+        assert_eq!(scale.items, 5000);
+        assert_eq!(scale.customers_per_district, 3000);
+        assert!(parse(&[("items", 0)]).is_err());
+        assert!(parse(&[("orders_per_district", 3001)]).is_err());
+    }
+}
@@ -4,7 +4,7 @@ use rand::{thread_rng, Rng};
 
 use crate::tpcc::random::rand_str;
 
-use super::{Warehouse, STOCKS_PER_WAREHOUSE};
+use super::{ScaleModel, Warehouse};
 
 #[derive(Debug)]
 pub struct Stock {
@@ -24,10 +24,10 @@ pub struct StockGenerator {
 }
 
 impl StockGenerator {
-    pub fn from_warehouse(warehouse: &Warehouse) -> Self {
+    pub fn from_warehouse(warehouse: &Warehouse, scale: &ScaleModel) -> Self {
         Self {
             warehouse_id: warehouse.id,
-            range: 1..=(STOCKS_PER_WAREHOUSE as _),
+            range: 1..=(scale.stocks_per_warehouse() as _),
         }
     }
 }
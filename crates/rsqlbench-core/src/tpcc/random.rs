@@ -10,6 +10,8 @@ use std::ops::RangeInclusive;
 use once_cell::sync::Lazy;
 use rand::{distributions::Alphanumeric, prelude::*};
 
+use super::model::ScaleModel;
+
 /// 4.3.2.2 The notation random a-string \[x .. y\] (respectively, n-string \[x .. y\]) represents a string of random
 /// alphanumeric (respectively, numeric) characters of a random length of minimum x, maximum y, and mean (y+x)/ 2.
 ///
@@ -35,18 +37,23 @@ pub fn rand_str(min_len: usize, max_len: usize) -> String {
 /// Given a number between 0 and 999, each of the three syllables is determined by the corresponding digit in the three
 /// digit representation of the number. For example, the number 371 generates the name PRICALLYOUGHT, and the
 /// number 40 generates the name BARPRESBAR.
-pub fn rand_last_name() -> String {
+pub fn last_name(number: usize) -> String {
     static TOKENS: [&str; 10] = [
         "BAR", "OUGHT", "ABLE", "PRI", "PRES", "ESE", "ANTI", "CALLY", "ATION", "EING",
     ];
-    let index = NURAND_LASTNAME.next(); // index = XYZ
     let mut name = String::with_capacity(15); // possible MAX length
-    name.push_str(TOKENS[index / 100]); // X
-    name.push_str(TOKENS[(index % 100) / 10]); // Y
-    name.push_str(TOKENS[index % 10]); // Z
+    name.push_str(TOKENS[number / 100]); // X
+    name.push_str(TOKENS[(number % 100) / 10]); // Y
+    name.push_str(TOKENS[number % 10]); // Z
     name
 }
 
+/// Last name generated by NURand(255,0,999), the range is narrowed for models with less than 1000 customers per
+/// district so that generated names always exist.
+pub fn rand_last_name(scale: &ScaleModel) -> String {
+    last_name(NURAND_LASTNAME.random(0, scale.customers_per_district.min(1000) - 1))
+}
+
 /// 4.3.2.5 The notation random within \[x .. y\] represents a random value independently selected and uniform ly
 /// distributed between x and y, inclusively, with a mean of (x+y)/ 2, and with the same number of digits of precision as
 /// shown. For example, \[0.01 .. 100.00\] has 10,000 unique values, whereas \[1 ..100\] has only 100 unique va lues.
@@ -90,6 +97,11 @@ impl NURand {
     pub fn next(&self) -> usize {
         self.random(*self.range.start(), *self.range.end())
     }
+
+    /// Generate within `start..=max` for scaled models, keeping the run-time constant C.
+    pub fn up_to(&self, max: usize) -> usize {
+        self.random(*self.range.start(), max)
+    }
 }
 
 pub struct NURandSpawner;
@@ -1,11 +1,18 @@
 mod generic;
 mod mysql;
+mod sql;
+
+use std::{future::Future, time::Duration};
 
 use async_trait::async_trait;
 pub use generic::*;
 pub use mysql::*;
+pub use sql::*;
+
+use crate::cfg::{tpcc::IsolationLevel, Role};
 
 use super::{
+    check::CheckError,
     loader::Loader,
     transaction::{
         Delivery, DeliveryOut, NewOrder, NewOrderOut, NewOrderRollbackOut, OrderStatus,
@@ -14,7 +21,7 @@ use super::{
 };
 
 #[async_trait]
-pub trait Sut {
+pub trait Sut: Sync {
     /// Make a terminal for simulate user.
     async fn terminal(&self, id: u32) -> anyhow::Result<Box<dyn Terminal>>;
 
@@ -27,12 +34,62 @@ pub trait Sut {
     /// Destroy schema created for TPC-C before.
     async fn destroy_schema(&self) -> anyhow::Result<()>;
 
+    /// Whether schema has been built, so that building could be skipped or forced.
+    async fn schema_exists(&self) -> anyhow::Result<bool> {
+        Err(CheckError::Unsupported("Checking existence of schema").into())
+    }
+
     /// Make a loader for loading data.
     async fn loader(&self) -> anyhow::Result<Box<dyn Loader>>;
+
+    /// Check configurations required by this SUT, such as credentials not in connection strings.
+    fn verify_config(&self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Try to connect to database with connection string and options of role.
+    async fn ping(&self, role: Role) -> anyhow::Result<()>;
+
+    /// Verify cardinalities of tables just after loaded, see [`super::check::expected_rows`].
+    async fn verify_load(&self) -> anyhow::Result<()> {
+        Err(CheckError::Unsupported("Verifying load").into())
+    }
+
+    /// Check consistency conditions, see [`super::check::CONSISTENCY_CONDITIONS`].
+    async fn check_consistency(&self) -> anyhow::Result<()> {
+        Err(CheckError::Unsupported("Checking consistency").into())
+    }
+}
+
+/// Transaction rolled back by database because of conflicts, such as deadlock, lock wait timeout or serialization
+/// failure. It's counted as an abort instead of failing the benchmark.
+#[derive(Debug, thiserror::Error)]
+#[error("Transaction aborted: {0}")]
+pub struct Aborted(pub String);
+
+/// Fail if `statement` does not finish within `limit`, which is enforced by client since statement timeout of server
+/// may not apply to procedure calls. Connection is in unknown state after timed out.
+pub async fn statement_timeout<T>(
+    limit: Option<Duration>,
+    statement: impl Future<Output = anyhow::Result<T>>,
+) -> anyhow::Result<T> {
+    match limit {
+        Some(limit) => tokio::time::timeout(limit, statement)
+            .await
+            .map_err(|_| anyhow::anyhow!("Statement timed out after {limit:?}"))?,
+        None => statement.await,
+    }
 }
 
 #[async_trait]
 pub trait Terminal: Send {
+    /// Set isolation level of all following transactions.
+    async fn set_isolation(&mut self, level: IsolationLevel) -> anyhow::Result<()> {
+        Err(anyhow::anyhow!(
+            "Isolation level {level} is not supported by this SUT"
+        ))
+    }
+
     async fn new_order(
         &mut self,
         input: &NewOrder,
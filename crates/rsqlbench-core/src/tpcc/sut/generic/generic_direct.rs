@@ -7,9 +7,8 @@ use tracing::info;
 
 use crate::tpcc::model::{
     Customer, CustomerGenerator, District, DistrictGenerator, History, HistoryGenerator, Item,
-    ItemGenerator, NewOrder, Order, OrderGenerator, OrderLine, OrderLineGenerator, Stock,
-    StockGenerator, Warehouse, CUSTOMER_PER_DISTRICT, DISTRICT_PER_WAREHOUSE, ORDERS_PER_DISTRICT,
-    STOCKS_PER_WAREHOUSE,
+    ItemGenerator, NewOrder, Order, OrderGenerator, OrderLine, OrderLineGenerator, ScaleModel,
+    Stock, StockGenerator, Warehouse,
 };
 
 pub trait Executor {
@@ -50,6 +49,7 @@ where
 
 pub async fn load_stocks(
     warehouse: &Warehouse,
+    scale: &ScaleModel,
     executor: &mut impl Executor,
     batch_size: usize,
 ) -> anyhow::Result<()> {
@@ -57,11 +57,9 @@ pub async fn load_stocks(
         "Loading stocks for warehouse ID={id} (batch size={batch_size})",
         id = warehouse.id
     );
-    assert!(STOCKS_PER_WAREHOUSE % batch_size == 0);
-
     const SQL: &str = "INSERT INTO stock (s_i_id, s_w_id, s_quantity, s_dist_01, s_dist_02, s_dist_03, s_dist_04, s_dist_05, s_dist_06, s_dist_07, s_dist_08, s_dist_09, s_dist_10, s_ytd, s_order_cnt, s_remote_cnt, s_data) VALUES ";
     let mut sql = SQL.to_string();
-    for stock in StockGenerator::from_warehouse(warehouse) {
+    for stock in StockGenerator::from_warehouse(warehouse, scale) {
         let Stock {
             item_id,
             warehouse_id,
@@ -73,17 +71,21 @@ pub async fn load_stocks(
             data,
         } = stock;
         sql.push_str(&format!("('{item_id}', '{warehouse_id}', '{quantity}', '{dist0}', '{dist1}', '{dist2}', '{dist3}', '{dist4}', '{dist5}', '{dist6}', '{dist7}', '{dist8}', '{dist9}', '{ytd}', '{order_count}', '{remote_count}', '{data}'),"));
-        if stock.item_id % (batch_size as u32) == 0 {
+        if stock.item_id.is_multiple_of(batch_size as u32) {
             executor.execute(&sql[0..sql.len() - 1]).await?;
             sql.clear();
             sql.push_str(SQL);
         }
     }
+    if sql.len() > SQL.len() {
+        executor.execute(&sql[0..sql.len() - 1]).await?;
+    }
     Ok(())
 }
 
 pub async fn load_warehouse(
     warehouse: &Warehouse,
+    scale: &ScaleModel,
     executor: &mut impl Executor,
 ) -> anyhow::Result<()> {
     let Warehouse {
@@ -98,22 +100,24 @@ pub async fn load_warehouse(
     } = warehouse;
     executor.execute(&format!("INSERT INTO warehouse (w_id, w_name, w_street_1, w_street_2, w_city, w_state, w_zip, w_tax, w_ytd) VALUES ('{id}', '{name}', '{street0}', '{street1}', '{city}', '{state}', '{zip}', '{tax}', '{ytd}')"))
         .await?;
-    load_stocks(warehouse, executor, 1000).await?;
-    load_districts(warehouse, executor).await?;
+    load_stocks(warehouse, scale, executor, 1000).await?;
+    load_districts(warehouse, scale, executor).await?;
     Ok(())
 }
 
-async fn load_districts(warehouse: &Warehouse, executor: &mut impl Executor) -> anyhow::Result<()> {
+async fn load_districts(
+    warehouse: &Warehouse,
+    scale: &ScaleModel,
+    executor: &mut impl Executor,
+) -> anyhow::Result<()> {
     let batch_size: usize = 10;
     info!(
         "Loading districts for warehouse ID={id} (batch size={batch_size})",
         id = warehouse.id,
     );
     const SQL: &str="INSERT INTO district (d_id, d_w_id, d_name, d_street_1, d_street_2, d_city, d_state, d_zip, d_tax, d_ytd, d_next_o_id) VALUES ";
-    assert!(DISTRICT_PER_WAREHOUSE % batch_size == 0);
-
     let mut sql = SQL.to_string();
-    for district in DistrictGenerator::from_warehouse(warehouse) {
+    for district in DistrictGenerator::from_warehouse(warehouse, scale) {
         let District {
             id,
             warehouse_id,
@@ -127,19 +131,29 @@ async fn load_districts(warehouse: &Warehouse, executor: &mut impl Executor) ->
             next_order_id,
         } = &district;
         sql.push_str(&format!("('{id}','{warehouse_id}','{name}','{street0}','{street1}','{city}','{state}','{zip}','{tax}','{ytd}','{next_order_id}'),"));
-        if district.id % (batch_size as u8) == 0 {
+        if district.id.is_multiple_of(batch_size as u8) {
             executor.execute(&sql[0..sql.len() - 1]).await?;
             sql.clear();
             sql.push_str(SQL);
         }
-        load_customers(&district, executor, CUSTOMER_PER_DISTRICT).await?;
-        load_orders(&district, executor, ORDERS_PER_DISTRICT / 2).await?;
+        load_customers(&district, scale, executor, scale.customers_per_district).await?;
+        load_orders(
+            &district,
+            scale,
+            executor,
+            scale.orders_per_district.div_ceil(2),
+        )
+        .await?;
+    }
+    if sql.len() > SQL.len() {
+        executor.execute(&sql[0..sql.len() - 1]).await?;
     }
     Ok(())
 }
 
 async fn load_customers(
     district: &District,
+    scale: &ScaleModel,
     executor: &mut impl Executor,
     batch_size: usize,
 ) -> anyhow::Result<()> {
@@ -148,13 +162,12 @@ async fn load_customers(
         d_id = district.id,
         id = district.warehouse_id,
     );
-    assert!(CUSTOMER_PER_DISTRICT % batch_size == 0);
     const CUSTOMER_SQL: &str = "INSERT INTO customer ( c_id, c_d_id, c_w_id, c_first, c_middle, c_last, c_street_1, c_street_2, c_city, c_state, c_zip, c_phone, c_since, c_credit, c_credit_lim, c_discount, c_balance, c_ytd_payment, c_payment_cnt, c_delivery_cnt, c_data) VALUES";
     const HISTORY_SQL: &str = "INSERT INTO history (h_c_id, h_c_d_id, h_c_w_id, h_d_id, h_w_id, h_date, h_amount, h_data) VALUES ";
 
     let mut customer_sql = CUSTOMER_SQL.to_string();
     let mut history_sql = HISTORY_SQL.to_string();
-    for customer in CustomerGenerator::from_district(district) {
+    for customer in CustomerGenerator::from_district(district, scale) {
         let Customer {
             id,
             district_id,
@@ -191,7 +204,7 @@ async fn load_customers(
             } = history;
             history_sql.push_str(&format!("('{customer_id}', '{customer_district_id}', '{customer_warehouse_id}', '{district_id}', '{warehouse_id}', NOW(), '{amount}', '{data}'),"));
         }
-        if customer.id % (batch_size as u16) == 0 {
+        if customer.id.is_multiple_of(batch_size as u16) {
             executor
                 .execute(&customer_sql[0..customer_sql.len() - 1])
                 .await?;
@@ -204,11 +217,20 @@ async fn load_customers(
             history_sql.push_str(HISTORY_SQL);
         }
     }
+    if customer_sql.len() > CUSTOMER_SQL.len() {
+        executor
+            .execute(&customer_sql[0..customer_sql.len() - 1])
+            .await?;
+        executor
+            .execute(&history_sql[0..history_sql.len() - 1])
+            .await?;
+    }
     Ok(())
 }
 
 async fn load_orders(
     district: &District,
+    scale: &ScaleModel,
     executor: &mut impl Executor,
     batch_size: usize,
 ) -> anyhow::Result<()> {
@@ -220,12 +242,11 @@ async fn load_orders(
     const ORDER_SQL: &str = "INSERT INTO oorder (o_id, o_d_id, o_w_id, o_c_id, o_entry_d, o_carrier_id, o_ol_cnt, o_all_local) VALUES";
     const NEW_ORDER_SQL: &str = "INSERT INTO new_order (no_o_id, no_d_id, no_w_id) VALUES ";
     const ORDER_LINE_SQL: &str = "INSERT INTO order_line (ol_o_id, ol_d_id, ol_w_id, ol_number, ol_i_id, ol_supply_w_id, ol_delivery_d, ol_quantity, ol_amount, ol_dist_info) VALUES";
-    assert!(ORDERS_PER_DISTRICT % batch_size == 0);
     let mut order_sql = ORDER_SQL.to_string();
     let mut new_order_sql = NEW_ORDER_SQL.to_string();
     let mut order_line_sql = ORDER_LINE_SQL.to_string();
 
-    for (order, new_order) in OrderGenerator::from_district(district) {
+    for (order, new_order) in OrderGenerator::from_district(district, scale) {
         let Order {
             id,
             district_id,
@@ -253,7 +274,7 @@ async fn load_orders(
             ));
         }
 
-        for ol in OrderLineGenerator::from_order(&order) {
+        for ol in OrderLineGenerator::from_order(&order, scale) {
             let OrderLine {
                 order_id,
                 district_id,
@@ -269,7 +290,7 @@ async fn load_orders(
             order_line_sql.push_str(&format!("('{order_id}', '{district_id}', '{warehouse_id}', '{number}', '{item_id}', '{supply_warehouse_id}', NOW(), '{quantity}', '{amount}', '{dist_info}'),"));
         }
 
-        if order.id % (batch_size as u32) == 0 {
+        if order.id.is_multiple_of(batch_size as u32) {
             executor.execute(&order_sql[0..order_sql.len() - 1]).await?;
             executor
                 .execute(&order_line_sql[0..order_line_sql.len() - 1])
@@ -289,6 +310,17 @@ async fn load_orders(
             }
         }
     }
+    if order_sql.len() > ORDER_SQL.len() {
+        executor.execute(&order_sql[0..order_sql.len() - 1]).await?;
+        executor
+            .execute(&order_line_sql[0..order_line_sql.len() - 1])
+            .await?;
+    }
+    if new_order_sql.ends_with(',') {
+        executor
+            .execute(&new_order_sql[0..new_order_sql.len() - 1])
+            .await?;
+    }
     Ok(())
 }
 
@@ -299,8 +331,6 @@ pub async fn load_items(
 ) -> anyhow::Result<()> {
     info!("Loading items (batch size={batch_size})");
     const SQL_PREFIX: &str = "INSERT INTO item (i_id, i_im_id, i_name, i_price, i_data) VALUES";
-    assert!(STOCKS_PER_WAREHOUSE % batch_size == 0);
-
     let mut sql = SQL_PREFIX.to_string();
     for (idx, item) in generator.enumerate() {
         let Item {
@@ -313,11 +343,14 @@ pub async fn load_items(
         sql.push_str(&format!(
             "('{id}','{image_id}', '{name}', '{price}', '{data}'),"
         ));
-        if (idx + 1) % batch_size == 0 {
+        if (idx + 1).is_multiple_of(batch_size) {
             executor.execute(&sql[0..sql.len() - 1]).await?;
             sql.clear();
             sql.push_str(SQL_PREFIX);
         }
     }
+    if sql.len() > SQL_PREFIX.len() {
+        executor.execute(&sql[0..sql.len() - 1]).await?;
+    }
     Ok(())
 }
@@ -0,0 +1,133 @@
+use anyhow::anyhow;
+use sqlx::{
+    mysql::{MySqlConnectOptions, MySqlSslMode},
+    ConnectOptions, Executor, MySqlConnection,
+};
+use tokio::{process::Command, time::timeout};
+
+use crate::cfg::{ConnectionOptions, TlsMode};
+
+/// Connect with TLS, timeout and session variables of options.
+pub async fn connect(url: &str, options: &ConnectionOptions) -> anyhow::Result<MySqlConnection> {
+    let mut connect_options: MySqlConnectOptions = url.parse()?;
+    if let Some(tls) = &options.tls {
+        connect_options = connect_options.ssl_mode(match tls.mode {
+            TlsMode::Disabled => MySqlSslMode::Disabled,
+            TlsMode::Preferred => MySqlSslMode::Preferred,
+            TlsMode::Required => MySqlSslMode::Required,
+            TlsMode::VerifyCa => MySqlSslMode::VerifyCa,
+            TlsMode::VerifyIdentity => MySqlSslMode::VerifyIdentity,
+        });
+        if let Some(ca) = &tls.ca {
+            connect_options = connect_options.ssl_ca(ca);
+        }
+        if let Some(cert) = &tls.cert {
+            connect_options = connect_options.ssl_client_cert(cert);
+        }
+        if let Some(key) = &tls.key {
+            connect_options = connect_options.ssl_client_key(key);
+        }
+    }
+    let mut conn = match options.connect_timeout {
+        Some(limit) => timeout(limit, connect_options.connect())
+            .await
+            .map_err(|_| anyhow!("Connecting timed out after {limit:?}"))??,
+        None => connect_options.connect().await?,
+    };
+    if let Some(sql) = session_statement(options)? {
+        conn.execute(sql.as_str()).await?;
+    }
+    Ok(conn)
+}
+
+/// Pass TLS, timeout and session variables of options to `mysql` command line client.
+pub fn client_args(command: &mut Command, options: &ConnectionOptions) -> anyhow::Result<()> {
+    if let Some(tls) = &options.tls {
+        command.arg(format!(
+            "--ssl-mode={}",
+            match tls.mode {
+                TlsMode::Disabled => "DISABLED",
+                TlsMode::Preferred => "PREFERRED",
+                TlsMode::Required => "REQUIRED",
+                TlsMode::VerifyCa => "VERIFY_CA",
+                TlsMode::VerifyIdentity => "VERIFY_IDENTITY",
+            }
+        ));
+        for (arg, path) in [("ca", &tls.ca), ("cert", &tls.cert), ("key", &tls.key)] {
+            if let Some(path) = path {
+                command.arg(format!("--ssl-{arg}={}", path.display()));
+            }
+        }
+    }
+    if let Some(limit) = options.connect_timeout {
+        command.arg(format!("--connect-timeout={}", limit.as_secs().max(1)));
+    }
+    if let Some(sql) = session_statement(options)? {
+        command.arg(format!("--init-command={sql}"));
+    }
+    Ok(())
+}
+
+/// `SET SESSION` statement of session variables, statement timeout is set as `max_execution_time` (which only
+/// limits top-level `SELECT` in MySQL, so that terminals calling procedures enforce it by client too).
+fn session_statement(options: &ConnectionOptions) -> anyhow::Result<Option<String>> {
+    let mut assignments = Vec::new();
+    if let Some(limit) = options.statement_timeout {
+        assignments.push(format!("max_execution_time = {}", limit.as_millis()));
+    }
+    for (name, value) in &options.session {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+        {
+            return Err(anyhow!("Invalid session variable name: {name:?}"));
+        }
+        assignments.push(format!("{name} = {}", session_value(value)));
+    }
+    Ok((!assignments.is_empty()).then(|| format!("SET SESSION {}", assignments.join(", "))))
+}
+
+/// Numbers and keywords are kept as is, others are quoted as strings.
+fn session_value(value: &str) -> String {
+    const KEYWORDS: [&str; 5] = ["ON", "OFF", "TRUE", "FALSE", "DEFAULT"];
+    if value.parse::<f64>().is_ok() || KEYWORDS.contains(&value.to_uppercase().as_str()) {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\\', "\\\\").replace('\'', "''"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::cfg::ConnectionOptions;
+
+    use super::session_statement;
+
+    #[test]
+    fn session() {
+        assert_eq!(
+            session_statement(&ConnectionOptions::default()).unwrap(),
+            None
+        );
+        let mut options = ConnectionOptions {
+            statement_timeout: Some(Duration::from_secs(30)),
+            ..Default::default()
+        };
+        options
+            .session
+            .insert("transaction_isolation".into(), "READ-COMMITTED".into());
+        options
+            .session
+            .insert("innodb_lock_wait_timeout".into(), "10".into());
+        options.session.insert("autocommit".into(), "on".into());
+        assert_eq!(
+            session_statement(&options).unwrap().unwrap(),
+            "SET SESSION max_execution_time = 30000, autocommit = on, innodb_lock_wait_timeout = 10, transaction_isolation = 'READ-COMMITTED'"
+        );
+        options.session.insert("x = 1; DROP".into(), "1".into());
+        assert!(session_statement(&options).is_err());
+    }
+}
@@ -1,20 +1,78 @@
+use std::{path::Path, time::Instant};
+
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
+use percent_encoding::percent_decode_str;
 use sqlx::{prelude::*, MySqlConnection};
+use tokio::process::Command;
 use tracing::{info, instrument};
+use url::Url;
 
-use crate::tpcc::{
-    loader::Loader,
-    model::{ItemGenerator, Warehouse},
-    sut::generic_direct::{self, SqlxExecutorWrapper},
+use crate::{
+    cfg::{ConnectionOptions, LoadMethod},
+    tpcc::{
+        csv,
+        loader::Loader,
+        model::{ItemGenerator, ScaleModel, Warehouse},
+        sut::{
+            generic_direct::{self, SqlxExecutorWrapper},
+            prepared,
+        },
+    },
 };
 
 pub struct MysqlLoader {
     conn: MySqlConnection,
+    url: String,
+    options: ConnectionOptions,
+    scale: ScaleModel,
+    method: LoadMethod,
 }
 
 impl MysqlLoader {
-    pub fn new(conn: MySqlConnection) -> Self {
-        Self { conn }
+    pub fn new(
+        conn: MySqlConnection,
+        url: String,
+        options: ConnectionOptions,
+        scale: ScaleModel,
+        method: LoadMethod,
+    ) -> Self {
+        Self {
+            conn,
+            url,
+            options,
+            scale,
+            method,
+        }
+    }
+
+    /// `mysql` command line client connecting to the same database as loader.
+    ///
+    /// SQLx does not support `LOAD DATA LOCAL INFILE`, the client is used instead.
+    fn mysql_client(&self) -> anyhow::Result<Command> {
+        let url = Url::parse(&self.url)?;
+        let decode = |s: &str| percent_decode_str(s).decode_utf8_lossy().into_owned();
+        let mut command = Command::new("mysql");
+        command.arg("--local-infile=1").arg("--batch");
+        if let Some(host) = url.host_str() {
+            command.arg(format!("--host={host}"));
+        }
+        if let Some(port) = url.port() {
+            command.arg(format!("--port={port}"));
+        }
+        if !url.username().is_empty() {
+            command.arg(format!("--user={}", decode(url.username())));
+        }
+        let database = url.path().trim_start_matches('/');
+        if !database.is_empty() {
+            command.arg(format!("--database={}", decode(database)));
+        }
+        if let Some(password) = url.password() {
+            // Keep password out of process list.
+            command.env("MYSQL_PWD", decode(password));
+        }
+        super::connect::client_args(&mut command, &self.options)?;
+        Ok(command)
     }
 }
 
@@ -22,18 +80,26 @@ impl MysqlLoader {
 impl Loader for MysqlLoader {
     #[instrument(skip(self, generator))]
     async fn load_items(&mut self, generator: ItemGenerator) -> anyhow::Result<()> {
+        let method = self.method;
+        let begin = Instant::now();
         self.conn
             .transaction(|txn| {
                 Box::pin(async move {
-                    generic_direct::load_items(
-                        generator,
-                        50000,
-                        &mut SqlxExecutorWrapper::new(&mut **txn),
-                    )
-                    .await
+                    match method {
+                        LoadMethod::Direct => {
+                            generic_direct::load_items(
+                                generator,
+                                50000,
+                                &mut SqlxExecutorWrapper::new(&mut **txn),
+                            )
+                            .await
+                        }
+                        LoadMethod::Prepared => prepared::load_items(generator, txn).await,
+                    }
                 })
             })
             .await?;
+        info!(?method, elapsed = ?begin.elapsed(), "Items loaded");
         Ok(())
     }
 
@@ -45,20 +111,60 @@ impl Loader for MysqlLoader {
         sqlx::query("set autocommit = 1")
             .execute(&mut self.conn)
             .await?;
+        let scale = self.scale;
+        let method = self.method;
         while let Ok(warehouse) = generator.recv().await {
             info!("Loading warehouse ID={id}", id = warehouse.id);
+            let begin = Instant::now();
             self.conn
                 .transaction(|txn| {
                     Box::pin(async move {
-                        generic_direct::load_warehouse(
-                            &warehouse,
-                            &mut SqlxExecutorWrapper::new(&mut **txn),
-                        )
-                        .await
+                        match method {
+                            LoadMethod::Direct => {
+                                generic_direct::load_warehouse(
+                                    &warehouse,
+                                    &scale,
+                                    &mut SqlxExecutorWrapper::new(&mut **txn),
+                                )
+                                .await
+                            }
+                            LoadMethod::Prepared => {
+                                prepared::load_warehouse(&warehouse, &scale, txn).await
+                            }
+                        }
                     })
                 })
                 .await?;
+            info!(?method, elapsed = ?begin.elapsed(), "Warehouse loaded");
+        }
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn load_csv(&mut self, table: &str, path: &Path) -> anyhow::Result<()> {
+        let columns = csv::columns(table).ok_or_else(|| anyhow!("Unknown table {table}"))?;
+        let path = path
+            .canonicalize()
+            .with_context(|| format!("Could not find {path:?}"))?;
+        let sql = format!(
+            "LOAD DATA LOCAL INFILE '{path}' INTO TABLE {table} FIELDS TERMINATED BY ',' LINES TERMINATED BY '\\n' ({columns})",
+            path = path.display().to_string().replace('\\', "\\\\").replace('\'', "\\'"),
+        );
+        let begin = Instant::now();
+        let output = self
+            .mysql_client()?
+            .arg("--execute")
+            .arg(&sql)
+            .output()
+            .await
+            .with_context(|| "Could not run mysql client, is it installed?")?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "Could not load {path:?}: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
         }
+        info!(elapsed = ?begin.elapsed(), "CSV file loaded");
         Ok(())
     }
 }
@@ -1,34 +1,105 @@
-use anyhow::Context;
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
 use async_trait::async_trait;
-use sqlx::{types::Decimal, MySqlConnection};
-use time::OffsetDateTime;
-
-use crate::tpcc::{
-    sut::Terminal,
-    transaction::{
-        CustomerSelector, Delivery, DeliveryOut, NewOrder, NewOrderLineOut, NewOrderOut,
-        NewOrderRollbackOut, OrderStatus, OrderStatusOut, Payment, PaymentOut, StockLevel,
-        StockLevelOut,
+use sqlx::{mysql::MySqlDatabaseError, types::Decimal, Executor, MySqlConnection};
+use time::{
+    format_description::FormatItem, macros::format_description, OffsetDateTime, PrimitiveDateTime,
+};
+
+use crate::{
+    cfg::tpcc::IsolationLevel,
+    tpcc::{
+        sut::{statement_timeout, Aborted, Terminal},
+        transaction::{
+            CustomerSelector, Delivery, DeliveryOut, NewOrder, NewOrderLineOut, NewOrderOut,
+            NewOrderRollbackOut, OrderStatus, OrderStatusLineOut, OrderStatusOut, Payment,
+            PaymentOut, StockLevel, StockLevelOut,
+        },
     },
 };
 
+/// Errors rolling back transactions: deadlock, lock wait timeout and serialization failure.
+const ABORT_ERRORS: [u16; 2] = [1213, 1205];
+
+/// `DATETIME` values of user variables and parameters, which are strings.
+const DATETIME_FORMAT: &[FormatItem] =
+    format_description!("[year]-[month]-[day] [hour]:[minute]:[second]");
+
+/// `DATETIME` is stored without time zone, which is taken as UTC.
+fn parse_datetime(datetime: &str) -> anyhow::Result<OffsetDateTime> {
+    Ok(PrimitiveDateTime::parse(datetime, DATETIME_FORMAT)
+        .with_context(|| format!("Invalid DATETIME: {datetime:?}"))?
+        .assume_utc())
+}
+
+/// Order lines returned by `OSTAT` as `supply_w_id,i_id,quantity,amount,delivery_d` separated by `;`, `delivery_d`
+/// is empty if not delivered yet.
+fn parse_order_lines(lines: &str) -> anyhow::Result<Vec<OrderStatusLineOut>> {
+    lines
+        .split(';')
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let fields = line.split(',').collect::<Vec<_>>();
+            let [warehouse_id, item_id, quantity, amount, delivery_date] = fields[..] else {
+                return Err(anyhow!("Invalid order line: {line:?}"));
+            };
+            Ok(OrderStatusLineOut {
+                item_id: item_id.parse()?,
+                warehouse_id: warehouse_id.parse()?,
+                quantity: quantity.parse()?,
+                amount: amount.parse()?,
+                delivery_date: match delivery_date {
+                    "" => None,
+                    date => Some(parse_datetime(date)?),
+                },
+            })
+        })
+        .collect()
+}
+
 pub struct MysqlTerminal {
     conn: MySqlConnection,
     warehouse_count: u32,
+    statement_timeout: Option<Duration>,
 }
 
+#[allow(clippy::type_complexity)] // values of user variables fetched as tuples
 impl MysqlTerminal {
-    pub fn new(conn: MySqlConnection, warehouse_count: u32) -> Self {
+    pub fn new(
+        conn: MySqlConnection,
+        warehouse_count: u32,
+        statement_timeout: Option<Duration>,
+    ) -> Self {
         Self {
             conn,
             warehouse_count,
+            statement_timeout,
         }
     }
-}
 
-#[async_trait]
-impl Terminal for MysqlTerminal {
-    async fn new_order(
+    /// Roll back and turn conflicts into [`Aborted`], lock wait timeout only rolls back the last statement so that the
+    /// transaction must be rolled back explicitly.
+    async fn check_aborted<T>(&mut self, result: anyhow::Result<T>) -> anyhow::Result<T> {
+        let Err(e) = result else {
+            return result;
+        };
+        let aborted = e
+            .downcast_ref::<sqlx::Error>()
+            .and_then(|e| e.as_database_error())
+            .and_then(|e| e.try_downcast_ref::<MySqlDatabaseError>())
+            .filter(|e| ABORT_ERRORS.contains(&e.number()) || e.code() == Some("40001"))
+            .map(|e| Aborted(e.to_string()));
+        match aborted {
+            Some(aborted) => {
+                self.conn.execute("ROLLBACK").await?;
+                Err(aborted.into())
+            }
+            None => Err(e),
+        }
+    }
+
+    async fn call_new_order(
         &mut self,
         input: &NewOrder,
     ) -> anyhow::Result<Result<NewOrderOut, NewOrderRollbackOut>> {
@@ -96,7 +167,7 @@ impl Terminal for MysqlTerminal {
         Ok(tx_result)
     }
 
-    async fn payment(&mut self, input: &Payment) -> anyhow::Result<PaymentOut> {
+    async fn call_payment(&mut self, input: &Payment) -> anyhow::Result<PaymentOut> {
         let (customer_id, name, by_name) = match &input.customer {
             CustomerSelector::LastName(name) => (None, Some(name), 1),
             CustomerSelector::ID(id) => (Some(id), None, 0),
@@ -111,10 +182,15 @@ impl Terminal for MysqlTerminal {
         let Payment {
             warehouse_id,
             district_id,
+            customer_warehouse_id,
+            customer_district_id,
             amount,
             ..
         } = input;
-        sqlx::query(&format!("CALL PAYMENT('{warehouse_id}', '{district_id}', '{warehouse_id}', '{district_id}', @customer_id, '{by_name}', '{amount}', @last_name, @street_1, @street_2, @city, @state, @zip, @d_street_1, @d_street_2, @d_city, @d_state, @d_zip, @first_name, @middle_name, @c_street_1, @c_street_2, @c_city, @c_state, @c_zip, @c_phone, @since, @credit, @credit_lim, @discount, @balance, @data, NOW())"))
+        // Bind the history date instead of `NOW()` to return it, `DATETIME` keeps seconds only.
+        let date = OffsetDateTime::now_utc().replace_nanosecond(0)?;
+        let timestamp = date.format(DATETIME_FORMAT)?;
+        sqlx::query(&format!("CALL PAYMENT('{warehouse_id}', '{district_id}', '{customer_warehouse_id}', '{customer_district_id}', @customer_id, '{by_name}', '{amount}', @last_name, @street_1, @street_2, @city, @state, @zip, @d_street_1, @d_street_2, @d_city, @d_state, @d_zip, @first_name, @middle_name, @c_street_1, @c_street_2, @c_city, @c_state, @c_zip, @c_phone, @since, @credit, @credit_lim, @discount, @balance, @data, '{timestamp}')"))
             .execute(&mut self.conn)
             .await?;
 
@@ -123,7 +199,7 @@ impl Terminal for MysqlTerminal {
                 .fetch_one(&mut self.conn)
                 .await
                 .with_context(||"Failed to fetch payment out")?;
-            let (c_city,c_state,c_zip,c_phone,_since,credit,credit_lim,discount,balance,data): (Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<Decimal>, Option<Decimal>, Option<Decimal>, Option<String>) = sqlx::query_as("select @c_city, @c_state, @c_zip, @c_phone, @since, @credit, @credit_lim, @discount, @balance, @data")
+            let (c_city,c_state,c_zip,c_phone,since,credit,credit_lim,discount,balance,data): (Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<String>, Option<Decimal>, Option<Decimal>, Option<Decimal>, Option<String>) = sqlx::query_as("select @c_city, @c_state, @c_zip, @c_phone, @since, @credit, @credit_lim, @discount, @balance, @data")
                 .fetch_one(&mut self.conn)
                 .await
                 .with_context(||"Failed to fetch payment out2")?;
@@ -131,10 +207,10 @@ impl Terminal for MysqlTerminal {
                 warehouse_id: *warehouse_id,
                 district_id: *district_id,
                 customer_id: customer_id as _,
-                customer_warehouse_id: input.customer_warehouse_id(),
-                customer_district_id: *district_id,
+                customer_warehouse_id: *customer_warehouse_id,
+                customer_district_id: *customer_district_id,
                 amount: *amount,
-                date: OffsetDateTime::now_utc(),
+                date,
                 warehouse_street: (street_1, street_2),
                 warehouse_city: city,
                 warehouse_state: state,
@@ -151,7 +227,7 @@ impl Terminal for MysqlTerminal {
                 customer_state: c_state,
                 customer_zip: c_zip,
                 customer_phone: c_phone,
-                customer_since: OffsetDateTime::now_utc(), // TODO parse out
+                customer_since: since.as_deref().map(parse_datetime).transpose()?,
                 customer_credit: credit,
                 customer_credit_lim: credit_lim.map(|x| x.try_into().unwrap()),
                 customer_discount: discount.map(|x| x.try_into().unwrap()),
@@ -162,7 +238,7 @@ impl Terminal for MysqlTerminal {
         Ok(out)
     }
 
-    async fn order_status(&mut self, input: &OrderStatus) -> anyhow::Result<OrderStatusOut> {
+    async fn call_order_status(&mut self, input: &OrderStatus) -> anyhow::Result<OrderStatusOut> {
         let (customer_id, name, by_name) = match &input.customer {
             CustomerSelector::LastName(name) => (None, Some(name), 1),
             CustomerSelector::ID(id) => (Some(id), None, 0),
@@ -179,10 +255,10 @@ impl Terminal for MysqlTerminal {
             district_id,
             ..
         } = input;
-        sqlx::query(&format!("CALL OSTAT('{warehouse_id}','{district_id}',@customer_id,'{by_name}',@last_name,@first_name,@middle_name,@balance,@order_id,@entry_date,@carrier_id)"))
+        sqlx::query(&format!("CALL OSTAT('{warehouse_id}','{district_id}',@customer_id,'{by_name}',@last_name,@first_name,@middle_name,@balance,@order_id,@entry_date,@carrier_id,@order_lines)"))
             .execute(&mut self.conn)
             .await?;
-        let (customer_id,last_name, first_name,middle_name,balance, order_id,entry_date, carrier_id) : (Option<i64>,Option<String>, Option<String>,Option<String>,Option<Decimal>,Option<i64>,Option<String>,Option<i64>) = sqlx::query_as("select @customer_id,@last_name,@first_name,@middle_name,@balance,@order_id,@entry_date,@carrier_id").fetch_one(&mut self.conn).await?;
+        let (customer_id,last_name, first_name,middle_name,balance, order_id,entry_date, carrier_id, order_lines) : (Option<i64>,Option<String>, Option<String>,Option<String>,Option<Decimal>,Option<i64>,Option<String>,Option<i64>,Option<String>) = sqlx::query_as("select @customer_id,@last_name,@first_name,@middle_name,@balance,@order_id,@entry_date,@carrier_id,@order_lines").fetch_one(&mut self.conn).await?;
         Ok(OrderStatusOut {
             warehouse_id: *warehouse_id,
             district_id: *district_id,
@@ -193,12 +269,16 @@ impl Terminal for MysqlTerminal {
             customer_balance: balance.map(|x| x.try_into().unwrap()),
             order_id: order_id.map(|x| x as u32),
             carrier_id: carrier_id.map(|x| x as u8),
-            entry_date: entry_date.map(|_| OffsetDateTime::now_utc()),
-            order_lines: vec![], // unimplemented for MySQL
+            entry_date: entry_date.as_deref().map(parse_datetime).transpose()?,
+            order_lines: order_lines
+                .as_deref()
+                .map(parse_order_lines)
+                .transpose()?
+                .unwrap_or_default(),
         })
     }
 
-    async fn delivery(&mut self, input: &Delivery) -> anyhow::Result<DeliveryOut> {
+    async fn call_delivery(&mut self, input: &Delivery) -> anyhow::Result<DeliveryOut> {
         sqlx::query(&format!(
             "CALL DELIVERY('{}','{}', NOW())",
             input.warehouse_id, input.carrier_id
@@ -211,7 +291,7 @@ impl Terminal for MysqlTerminal {
         })
     }
 
-    async fn stock_level(&mut self, input: &StockLevel) -> anyhow::Result<StockLevelOut> {
+    async fn call_stock_level(&mut self, input: &StockLevel) -> anyhow::Result<StockLevelOut> {
         let StockLevel {
             warehouse_id,
             district_id,
@@ -233,3 +313,87 @@ impl Terminal for MysqlTerminal {
         })
     }
 }
+
+#[async_trait]
+impl Terminal for MysqlTerminal {
+    async fn set_isolation(&mut self, level: IsolationLevel) -> anyhow::Result<()> {
+        self.conn
+            .execute(format!("SET SESSION TRANSACTION ISOLATION LEVEL {}", level.sql()).as_str())
+            .await?;
+        Ok(())
+    }
+
+    async fn new_order(
+        &mut self,
+        input: &NewOrder,
+    ) -> anyhow::Result<Result<NewOrderOut, NewOrderRollbackOut>> {
+        let result = statement_timeout(self.statement_timeout, self.call_new_order(input)).await;
+        self.check_aborted(result).await
+    }
+
+    async fn payment(&mut self, input: &Payment) -> anyhow::Result<PaymentOut> {
+        let result = statement_timeout(self.statement_timeout, self.call_payment(input)).await;
+        self.check_aborted(result).await
+    }
+
+    async fn order_status(&mut self, input: &OrderStatus) -> anyhow::Result<OrderStatusOut> {
+        let result = statement_timeout(self.statement_timeout, self.call_order_status(input)).await;
+        self.check_aborted(result).await
+    }
+
+    async fn delivery(&mut self, input: &Delivery) -> anyhow::Result<DeliveryOut> {
+        let result = statement_timeout(self.statement_timeout, self.call_delivery(input)).await;
+        self.check_aborted(result).await
+    }
+
+    async fn stock_level(&mut self, input: &StockLevel) -> anyhow::Result<StockLevelOut> {
+        let result = statement_timeout(self.statement_timeout, self.call_stock_level(input)).await;
+        self.check_aborted(result).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use time::macros::datetime;
+
+    use super::{parse_datetime, parse_order_lines, DATETIME_FORMAT};
+
+    #[test]
+    fn datetime() {
+        assert_eq!(
+            parse_datetime("2024-07-15 08:30:05").unwrap(),
+            datetime!(2024-07-15 08:30:05 UTC)
+        );
+        assert_eq!(
+            datetime!(2024-07-15 08:30:05 UTC)
+                .format(DATETIME_FORMAT)
+                .unwrap(),
+            "2024-07-15 08:30:05"
+        );
+        assert!(parse_datetime("15-07-2024").is_err());
+    }
+
+    #[test]
+    fn order_lines() {
+        assert!(parse_order_lines("").unwrap().is_empty());
+        let lines = parse_order_lines("1,2034,5,99.50,2024-07-15 08:30:05;3,77,10,0.00,").unwrap();
+        assert_eq!(lines.len(), 2);
+        let (first, second) = (&lines[0], &lines[1]);
+        assert_eq!(
+            (
+                first.warehouse_id,
+                first.item_id,
+                first.quantity,
+                first.amount
+            ),
+            (1, 2034, 5, 99.5)
+        );
+        assert_eq!(
+            first.delivery_date,
+            Some(datetime!(2024-07-15 08:30:05 UTC))
+        );
+        assert_eq!((second.warehouse_id, second.item_id), (3, 77));
+        assert_eq!(second.delivery_date, None);
+        assert!(parse_order_lines("1,2034,5").is_err());
+    }
+}
@@ -0,0 +1,156 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context};
+use tracing::info;
+
+use crate::{cfg::Schema, tpcc::model::ScaleModel};
+
+/// SQL files used by SUT, files in user directory take precedence over built-in ones.
+///
+/// Placeholders like `{{database}}` are replaced by variables before returned.
+#[derive(Debug, Clone)]
+pub struct SqlFiles {
+    dir: Option<PathBuf>,
+    builtin: &'static [(&'static str, &'static str)],
+    vars: Vec<(&'static str, String)>,
+}
+
+impl SqlFiles {
+    pub fn new(dir: Option<PathBuf>, builtin: &'static [(&'static str, &'static str)]) -> Self {
+        Self {
+            dir,
+            builtin,
+            vars: vec![],
+        }
+    }
+
+    /// SQL files with variables `{{database}}`, `{{warehouses}}`, `{{partitions}}` and `{{items}}` defined.
+    pub fn from_schema(
+        schema: &Schema,
+        database: &str,
+        warehouse_count: u32,
+        scale: &ScaleModel,
+        builtin: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        Self::new(schema.sql_dir.clone(), builtin)
+            .var("database", database)
+            .var("warehouses", warehouse_count)
+            .var("partitions", schema.partitions(warehouse_count))
+            .var("items", scale.items)
+    }
+
+    /// Define variable `{{name}}`.
+    pub fn var(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.vars.push((name, value.to_string()));
+        self
+    }
+
+    /// Content of file `name`, such as `tables/item.sql`.
+    pub fn get(&self, name: &str) -> anyhow::Result<String> {
+        let text = match self.user_file(name) {
+            Some(path) => {
+                info!(?path, "Using user provided SQL file");
+                std::fs::read_to_string(&path)
+                    .with_context(|| format!("Could not read SQL file {path:?}"))?
+            }
+            None => self
+                .builtin
+                .iter()
+                .find(|(n, _)| *n == name)
+                .map(|(_, text)| text.to_string())
+                .ok_or_else(|| anyhow!("No SQL file named {name}"))?,
+        };
+        self.render(name, text)
+    }
+
+    /// Statements in file `name` separated by `;` at end of line.
+    pub fn statements(&self, name: &str) -> anyhow::Result<Vec<String>> {
+        Ok(split_statements(&self.get(name)?))
+    }
+
+    fn user_file(&self, name: &str) -> Option<PathBuf> {
+        self.dir
+            .as_deref()
+            .map(|dir| Path::join(dir, name))
+            .filter(|path| path.is_file())
+    }
+
+    fn render(&self, name: &str, mut text: String) -> anyhow::Result<String> {
+        for (var, value) in &self.vars {
+            text = text.replace(&format!("{{{{{var}}}}}"), value);
+        }
+        if let Some(begin) = text.find("{{") {
+            let end = text[begin..]
+                .find("}}")
+                .map_or(text.len(), |e| begin + e + 2);
+            return Err(anyhow!(
+                "Unknown variable {} in SQL file {name}",
+                &text[begin..end]
+            ));
+        }
+        Ok(text)
+    }
+}
+
+fn split_statements(text: &str) -> Vec<String> {
+    let mut statements = vec![];
+    let mut current = String::new();
+    for line in text.lines() {
+        let trimmed = line.trim_end();
+        if let Some(last) = trimmed.strip_suffix(';') {
+            current.push_str(last);
+            statements.push(std::mem::take(&mut current));
+        } else {
+            current.push_str(line);
+            current.push('\n');
+        }
+    }
+    statements.push(current);
+    statements
+        .into_iter()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::SqlFiles;
+
+    static BUILTIN: &[(&str, &str)] = &[
+        (
+            "indexes.sql",
+            "CREATE INDEX i1 ON t1 (a);\nCREATE INDEX i2\n  ON t2 (b);\n",
+        ),
+        ("db.sql", "CREATE DATABASE {{database}} -- {{partitions}}"),
+    ];
+
+    #[test]
+    fn builtin_and_override() {
+        let dir = std::env::temp_dir().join(format!("rsqlbench-sql-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("indexes.sql"), "CREATE INDEX i3 ON t3 (c)").unwrap();
+
+        let builtin = SqlFiles::new(None, BUILTIN);
+        assert_eq!(
+            builtin.statements("indexes.sql").unwrap(),
+            ["CREATE INDEX i1 ON t1 (a)", "CREATE INDEX i2\n  ON t2 (b)"]
+        );
+        let user = SqlFiles::new(Some(dir.clone()), BUILTIN);
+        assert_eq!(
+            user.statements("indexes.sql").unwrap(),
+            ["CREATE INDEX i3 ON t3 (c)"]
+        );
+        assert!(user.get("db.sql").is_err());
+        assert!(user.get("missing.sql").is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn render() {
+        let files = SqlFiles::new(None, BUILTIN)
+            .var("database", "tpcc")
+            .var("partitions", 8);
+        assert_eq!(files.get("db.sql").unwrap(), "CREATE DATABASE tpcc -- 8");
+    }
+}
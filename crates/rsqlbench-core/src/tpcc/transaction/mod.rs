@@ -13,7 +13,7 @@ pub use stock_level::*;
 
 use std::{fmt::Display, time::Duration};
 
-use crate::cfg::tpcc::TpccTransaction;
+use crate::{cfg::tpcc::TpccTransaction, tpcc::model::ScaleModel};
 
 pub const DATE_TIME_FORMAT: &[time::format_description::FormatItem] =
     time::macros::format_description!("[day]-[month]-[year] [hour]:[minute]:[second]");
@@ -32,6 +32,7 @@ pub enum Transaction {
 impl Transaction {
     pub fn generate(
         tx_weights: &TpccTransaction,
+        scale: &ScaleModel,
         warehouse_id: u32,
         district_id: u8,
         warehouse_count: u32,
@@ -42,9 +43,10 @@ impl Transaction {
                 warehouse_id,
                 warehouse_count,
                 district_id,
+                scale,
             ))
         } else if picker < tx_weights.payment + tx_weights.order_status {
-            Transaction::OrderStatus(OrderStatus::generate(warehouse_id))
+            Transaction::OrderStatus(OrderStatus::generate(warehouse_id, scale))
         } else if picker < tx_weights.payment + tx_weights.order_status + tx_weights.delivery {
             Transaction::Delivery(Delivery::generate(warehouse_id))
         } else if picker
@@ -55,7 +57,7 @@ impl Transaction {
         {
             Transaction::StockLevel(StockLevel::generate(warehouse_id, district_id))
         } else {
-            Transaction::NewOrder(NewOrder::generate(warehouse_id, warehouse_count))
+            Transaction::NewOrder(NewOrder::generate(warehouse_id, warehouse_count, scale))
         }
     }
 
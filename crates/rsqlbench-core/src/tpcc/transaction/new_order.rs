@@ -37,7 +37,7 @@ use rand::{thread_rng, Rng};
 use time::OffsetDateTime;
 
 use crate::tpcc::{
-    model::{DISTRICT_PER_WAREHOUSE, MAX_ITEMS},
+    model::{ScaleModel, DISTRICT_PER_WAREHOUSE},
     random::{NURAND_CUSTOMER_ID, NURAND_ITEM_ID},
 };
 
@@ -53,7 +53,7 @@ pub struct NewOrder {
 }
 
 impl NewOrder {
-    pub fn generate(warehouse_id: u32, warehouse_count: u32) -> NewOrder {
+    pub fn generate(warehouse_id: u32, warehouse_count: u32, scale: &ScaleModel) -> NewOrder {
         let rollback_last = thread_rng().gen_bool(0.01);
         let mut order_lines = (1..=(thread_rng().gen_range(5..=15)))
             .map(|_| {
@@ -65,7 +65,7 @@ impl NewOrder {
                     }
                 }
                 NewOrderLine {
-                    item_id: NURAND_ITEM_ID.next() as _,
+                    item_id: NURAND_ITEM_ID.up_to(scale.items) as _,
                     warehouse_id: w_id,
                     quantity: thread_rng().gen_range(1..=10),
                     original_warehouse_id: warehouse_id,
@@ -73,13 +73,13 @@ impl NewOrder {
             })
             .collect::<Vec<_>>();
         if rollback_last {
-            order_lines.last_mut().unwrap().item_id = MAX_ITEMS as u32 + 1;
+            order_lines.last_mut().unwrap().item_id = scale.items as u32 + 1;
         }
         Self {
             warehouse_id,
             district_id: thread_rng().gen_range(1..=(DISTRICT_PER_WAREHOUSE as u8)),
             rollback_last,
-            customer_id: NURAND_CUSTOMER_ID.next() as _,
+            customer_id: NURAND_CUSTOMER_ID.up_to(scale.customers_per_district) as _,
             order_lines,
         }
     }
@@ -302,13 +302,13 @@ Execution Status: Item number is not valid                   Total:  $-----.--
 mod test {
     use time::OffsetDateTime;
 
-    use crate::tpcc::transaction::test::terminal_display;
+    use crate::tpcc::{model::ScaleModel, transaction::test::terminal_display};
 
     use super::{NewOrder, NewOrderLineOut, NewOrderOut, NewOrderRollbackOut};
 
     #[test]
     fn display() {
-        terminal_display(NewOrder::generate(1, 2));
+        terminal_display(NewOrder::generate(1, 2, &ScaleModel::SPEC));
     }
 
     #[test]
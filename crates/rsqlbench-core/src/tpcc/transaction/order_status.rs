@@ -34,7 +34,7 @@ use std::fmt::Display;
 use rand::{thread_rng, Rng};
 use time::OffsetDateTime;
 
-use crate::tpcc::model::DISTRICT_PER_WAREHOUSE;
+use crate::tpcc::model::{ScaleModel, DISTRICT_PER_WAREHOUSE};
 
 use super::{CustomerSelector, SimpleOptionWrapper, DATE_TIME_FORMAT, ONLY_DATE_FORMAT};
 
@@ -46,11 +46,11 @@ pub struct OrderStatus {
 }
 
 impl OrderStatus {
-    pub fn generate(warehouse_id: u32) -> Self {
+    pub fn generate(warehouse_id: u32, scale: &ScaleModel) -> Self {
         Self {
             warehouse_id,
             district_id: thread_rng().gen_range(1..(DISTRICT_PER_WAREHOUSE as _)),
-            customer: CustomerSelector::generate(),
+            customer: CustomerSelector::generate(scale),
         }
     }
 }
@@ -172,7 +172,8 @@ pub struct OrderStatusLineOut {
     pub warehouse_id: u32,
     pub quantity: u8,
     pub amount: f32,
-    pub delivery_date: OffsetDateTime,
+    /// `None` if not delivered yet.
+    pub delivery_date: Option<OffsetDateTime>,
 }
 
 impl Display for OrderStatusLineOut {
@@ -184,11 +185,13 @@ impl Display for OrderStatusLineOut {
             amount: a,
             delivery_date: date,
         } = self;
-        let date = date.format(ONLY_DATE_FORMAT).unwrap();
+        let date = date
+            .map(|x| x.format(ONLY_DATE_FORMAT).unwrap())
+            .unwrap_or_default();
         //         Supply-W     Item-Id    Qty     Amount      Delivery-Date
         write!(
             f,
-            "  {w:<6}      {i:<6}     {q:<2}     ${a:<7.2}      {date}                        "
+            "  {w:<6}      {i:<6}     {q:<2}     ${a:<7.2}      {date:<10}                        "
         )?;
         Ok(())
     }
@@ -198,13 +201,13 @@ impl Display for OrderStatusLineOut {
 mod test {
     use time::OffsetDateTime;
 
-    use crate::tpcc::transaction::test::terminal_display;
+    use crate::tpcc::{model::ScaleModel, transaction::test::terminal_display};
 
     use super::{OrderStatus, OrderStatusLineOut, OrderStatusOut};
 
     #[test]
     fn display() {
-        terminal_display(OrderStatus::generate(22));
+        terminal_display(OrderStatus::generate(22, &ScaleModel::SPEC));
     }
 
     #[test]
@@ -225,7 +228,7 @@ mod test {
                 warehouse_id: 2,
                 quantity: 50,
                 amount: 100.0,
-                delivery_date: OffsetDateTime::now_utc(),
+                delivery_date: Some(OffsetDateTime::now_utc()),
             }],
         });
     }
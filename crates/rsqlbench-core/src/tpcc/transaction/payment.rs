@@ -38,7 +38,7 @@ use rand::{thread_rng, Rng};
 use time::OffsetDateTime;
 
 use crate::tpcc::{
-    model::DISTRICT_PER_WAREHOUSE,
+    model::{ScaleModel, DISTRICT_PER_WAREHOUSE},
     random::{rand_double, rand_last_name, NURAND_CUSTOMER_ID},
 };
 
@@ -51,47 +51,47 @@ use super::{
 pub struct Payment {
     pub warehouse_id: u32,
     pub district_id: u8,
+    /// Warehouse of customer, which is remote in 15% of payments (Clause 2.5.1.2).
+    pub customer_warehouse_id: u32,
+    pub customer_district_id: u8,
     pub customer: CustomerSelector,
     pub amount: f32,
-    preferred_warehouse_id: u32,
 }
 
 impl Payment {
     pub fn generate(
-        preferred_warehouse_id: u32,
+        warehouse_id: u32,
         warehouse_count: u32,
-        preferred_district_id: u8,
+        district_id: u8,
+        scale: &ScaleModel,
     ) -> Self {
-        let (warehouse_id, district_id) = if thread_rng().gen_bool(0.85) {
-            (preferred_warehouse_id, preferred_district_id)
+        let (customer_warehouse_id, customer_district_id) = if thread_rng().gen_bool(0.85) {
+            (warehouse_id, district_id)
         } else {
-            let mut w_id = preferred_warehouse_id;
-            if thread_rng().gen_bool(0.01) && warehouse_count > 1 {
+            let mut c_w_id = warehouse_id;
+            if warehouse_count > 1 {
                 // remote warehouse
-                while w_id == preferred_warehouse_id {
-                    w_id = thread_rng().gen_range(1..=warehouse_count);
+                while c_w_id == warehouse_id {
+                    c_w_id = thread_rng().gen_range(1..=warehouse_count);
                 }
             }
             (
-                w_id,
+                c_w_id,
                 thread_rng().gen_range(1..=(DISTRICT_PER_WAREHOUSE as u8)),
             )
         };
         Self {
             warehouse_id,
             district_id,
-            customer: CustomerSelector::generate(),
+            customer_warehouse_id,
+            customer_district_id,
+            customer: CustomerSelector::generate(scale),
             amount: rand_double(1.00, 5000.00, -2) as f32,
-            preferred_warehouse_id,
         }
     }
 
-    pub fn customer_warehouse_id(&self) -> u32 {
-        self.preferred_warehouse_id
-    }
-
     pub fn is_remote(&self) -> bool {
-        self.preferred_warehouse_id == self.warehouse_id
+        self.customer_warehouse_id != self.warehouse_id
     }
 }
 
@@ -101,9 +101,10 @@ impl Display for Payment {
         let Self {
             warehouse_id: w,
             district_id: d,
+            customer_warehouse_id: cw,
+            customer_district_id: cd,
             customer,
             amount: a,
-            preferred_warehouse_id: pw,
         } = self;
         let (c_, c_last_____) = match customer {
             CustomerSelector::LastName(l) => (String::from("----"), l.as_str()),
@@ -119,7 +120,7 @@ XXXXXXXXXXXXXXXXXXXX                     XXXXXXXXXXXXXXXXXXXX
 XXXXXXXXXXXXXXXXXXXX                     XXXXXXXXXXXXXXXXXXXX                   
 XXXXXXXXXXXXXXXXXXXX XX XXXXX-XXXX       XXXXXXXXXXXXXXXXXXXX XX XXXXX-XXXX     
                                                                                 
-Customer: {c_}  Cust-Warehouse: {pw:<4}  Cust-District: {d:<2}                         
+Customer: {c_}  Cust-Warehouse: {cw:<4}  Cust-District: {cd:<2}                         
 Name:   {c_last_____:16} XX XXXXXXXXXXXXXXXX     Since:  **-**-****             
         XXXXXXXXXXXXXXXXXXXX                     Credit: XX                     
         XXXXXXXXXXXXXXXXXXXX                     %Disc:  --.--                  
@@ -147,11 +148,11 @@ pub enum CustomerSelector {
 }
 
 impl CustomerSelector {
-    pub fn generate() -> Self {
+    pub fn generate(scale: &ScaleModel) -> Self {
         if thread_rng().gen_bool(0.6) {
-            CustomerSelector::LastName(rand_last_name())
+            CustomerSelector::LastName(rand_last_name(scale))
         } else {
-            CustomerSelector::ID(NURAND_CUSTOMER_ID.next() as _)
+            CustomerSelector::ID(NURAND_CUSTOMER_ID.up_to(scale.customers_per_district) as _)
         }
     }
 }
@@ -181,7 +182,7 @@ pub struct PaymentOut {
     pub customer_state: Option<String>,
     pub customer_zip: Option<String>,
     pub customer_phone: Option<String>,
-    pub customer_since: OffsetDateTime,
+    pub customer_since: Option<OffsetDateTime>,
     pub customer_credit: Option<String>,
     pub customer_credit_lim: Option<f32>,
     pub customer_discount: Option<f32>,
@@ -227,7 +228,9 @@ impl Display for PaymentOut {
         let d_zip = ZipWrapper(d_zip);
         let c_zip = OptionWrapper(c_zip.as_ref().map(|x| x.as_str()), ZipWrapper);
         let c_phone = OptionWrapper(customer_phone.as_ref().map(|x| x.as_str()), PhoneWrapper);
-        let c_since = c_since.format(ONLY_DATE_FORMAT).unwrap();
+        let c_since = c_since
+            .map(|x| x.format(ONLY_DATE_FORMAT).unwrap())
+            .unwrap_or_default();
         let (c_data1, c_data2, c_data3, c_data4) = match customer_data {
             Some(data) => (
                 &data[0..50],
@@ -262,7 +265,7 @@ Warehouse: {w:<6}                        District: {d:<2}
 {warehouse_city:<20} {warehouse_state:<2} {w_zip}       {district_city:<20} {district_state:<2} {d_zip}     
                                                                                 
 Customer: {c_:<4}  Cust-Warehouse: {cw:<4}  Cust-District: {cd:<2}                         
-Name:   {c_last_____:16} {customer_middle_name:<2} {customer_first_name:<16}     Since:  {c_since}             
+Name:   {c_last_____:16} {customer_middle_name:<2} {customer_first_name:<16}     Since:  {c_since:<10}             
         {c_street1_____:<20}                     Credit: {customer_credit}                     
         {c_street2_____:<20}                     %Disc:  {customer_discount:<5.2}                  
         {customer_city_:<20} {customer_state:<2} {c_zip:<10}       Phone:  {c_phone:<19}    
@@ -286,13 +289,36 @@ Cust-Data: {c_data1:<50}
 mod test {
     use time::OffsetDateTime;
 
-    use crate::tpcc::transaction::{test::terminal_display, Payment};
+    use crate::tpcc::{
+        model::ScaleModel,
+        transaction::{test::terminal_display, Payment},
+    };
 
     use super::PaymentOut;
 
     #[test]
     fn display() {
-        terminal_display(Payment::generate(29, 30, 29));
+        terminal_display(Payment::generate(29, 30, 9, &ScaleModel::SPEC));
+    }
+
+    #[test]
+    fn remote() {
+        let payments: Vec<_> = (0..1000)
+            .map(|_| Payment::generate(2, 3, 4, &ScaleModel::SPEC))
+            .collect();
+        for p in &payments {
+            assert_eq!((p.warehouse_id, p.district_id), (2, 4));
+            assert!((1..=3).contains(&p.customer_warehouse_id));
+            assert!((1..=10).contains(&p.customer_district_id));
+            if !p.is_remote() {
+                assert_eq!(p.customer_district_id, 4);
+            }
+        }
+        let remote = payments.iter().filter(|p| p.is_remote()).count();
+        assert!((50..300).contains(&remote), "{remote} remote payments");
+
+        let p = Payment::generate(1, 1, 4, &ScaleModel::SPEC);
+        assert!(!p.is_remote());
     }
 
     #[test]
@@ -321,7 +347,7 @@ mod test {
             customer_state: Some("GT".to_string()),
             customer_zip: Some("444488585".to_string()),
             customer_phone: Some("1234567890123456".to_string()),
-            customer_since: OffsetDateTime::now_utc(),
+            customer_since: Some(OffsetDateTime::now_utc()),
             customer_credit: Some("GC".to_string()),
             customer_credit_lim: Some(123.45),
             customer_discount: Some(90.0),
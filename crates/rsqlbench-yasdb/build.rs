@@ -1,15 +1,32 @@
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 fn main() {
     let src_dir = env!("CARGO_MANIFEST_DIR");
+    let header = format!("{src_dir}/yascli/include/yacli.h");
+    let dynamic = env::var_os("CARGO_FEATURE_DYNAMIC").is_some();
 
-    println!("cargo:rustc-link-search={src_dir}/yascli/lib");
-    println!("cargo:rustc-link-lib=yascli");
-    println!("cargo:rustc-link-lib=yas_infra");
-    println!("cargo:rerun-if-changed={src_dir}/yascli/include/yacli.h");
+    println!("cargo:rustc-check-cfg=cfg(yacli_header)");
+    println!("cargo:rerun-if-changed={header}");
+    println!("cargo:rerun-if-env-changed=YACLI_UNCHECKED");
+    if dynamic {
+        // Library is loaded at runtime, header is still required to check declared constants, which are passed to
+        // library as they are.
+        if !Path::new(&header).exists() {
+            if env::var_os("YACLI_UNCHECKED").is_some() {
+                println!("cargo:warning=Declarations of yascli are not checked without {header}");
+                return;
+            }
+            panic!("{header} is required to check declarations of yascli, download it by `scripts/download-yascli.sh` or set `YACLI_UNCHECKED=1` to build unchecked(for unit tests with fake driver only)");
+        }
+        println!("cargo:rustc-cfg=yacli_header");
+    } else {
+        println!("cargo:rustc-link-search={src_dir}/yascli/lib");
+        println!("cargo:rustc-link-lib=yascli");
+        println!("cargo:rustc-link-lib=yas_infra");
+    }
     let bindings = bindgen::Builder::default()
-        .header(format!("{src_dir}/yascli/include/yacli.h"))
+        .header(header)
         .parse_callbacks(Box::new(bindgen::CargoCallbacks::new()))
         .generate()
         .expect("Unable to generate bindings");
@@ -0,0 +1,8 @@
+CREATE UNIQUE INDEX CUSTOMER_I1 ON CUSTOMER (C_W_ID, C_D_ID, C_ID);
+CREATE UNIQUE INDEX CUSTOMER_I2 ON CUSTOMER (C_LAST, C_D_ID, C_W_ID, C_FIRST);
+CREATE UNIQUE INDEX DISTRICT_I1 ON DISTRICT (D_W_ID, D_ID);
+CREATE UNIQUE INDEX ITEM_I1 ON ITEM (I_ID);
+CREATE UNIQUE INDEX OORDER_I1 ON OORDER (O_W_ID, O_D_ID, O_ID);
+CREATE UNIQUE INDEX OORDER_I2 ON OORDER (O_W_ID, O_D_ID, O_C_ID, O_ID);
+CREATE UNIQUE INDEX STOCK_I1 ON STOCK (S_I_ID, S_W_ID);
+CREATE UNIQUE INDEX WAREHOUSE_I1 ON WAREHOUSE (W_ID);
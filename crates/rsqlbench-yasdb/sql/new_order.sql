@@ -5,7 +5,7 @@ OR REPLACE PROCEDURE NEWORD (
   no_d_id BINARY_INTEGER,
   no_c_id BINARY_INTEGER,
   no_o_ol_cnt BINARY_INTEGER,
-  no_rollback BOOLEAN,
+  no_rollback BINARY_INTEGER,
   no_c_discount OUT NUMBER,
   no_c_last OUT VARCHAR2,
   no_c_credit OUT VARCHAR2,
@@ -16,6 +16,7 @@ OR REPLACE PROCEDURE NEWORD (
 ) IS order_amount NUMBER;
 no_o_all_local BINARY_INTEGER;
 loop_counter BINARY_INTEGER;
+max_i_id BINARY_INTEGER;
 -- TODO yashan db????
 --             not_serializable		EXCEPTION;
 --             PRAGMA EXCEPTION_INIT(not_serializable,-8177);
@@ -56,9 +57,10 @@ WHERE
   AND customer.c_id = no_c_id;
 --#2.4.1.5
   no_o_all_local := 1;
+max_i_id := {{items}};
 FOR loop_counter IN 1..no_o_ol_cnt
 LOOP
-  o_id_array(loop_counter) := round(DBMS_RANDOM.value(low => 1, high => 100000));
+  o_id_array(loop_counter) := round(DBMS_RANDOM.value(low => 1, high => max_i_id));
 --#2.4.1.5.2
   IF (DBMS_RANDOM.value >= 0.01) THEN w_id_array(loop_counter) := no_w_id;
   ELSE no_o_all_local := 0;
@@ -423,7 +425,7 @@ VALUES
     district_info(i)
   );
 -- Rollback 1% of transactions
-  IF no_rollback THEN dbms_output.put_line('Rolling back');
+  IF no_rollback = 1 THEN dbms_output.put_line('Rolling back');
 ROLLBACK;
   ELSE COMMIT;
 END
@@ -0,0 +1 @@
+CREATE OR REPLACE VIEW STOCK_ITEM (I_ID, S_W_ID, I_PRICE, I_NAME, I_DATA, S_DATA, S_QUANTITY, S_ORDER_CNT, S_YTD, S_REMOTE_CNT, S_DIST_01, S_DIST_02, S_DIST_03, S_DIST_04, S_DIST_05, S_DIST_06, S_DIST_07, S_DIST_08, S_DIST_09, S_DIST_10) AS SELECT /*+ LEADING(S) USE_NL(I) */ I.I_ID, S_W_ID, I.I_PRICE, I.I_NAME, I.I_DATA, S_DATA, S_QUANTITY, S_ORDER_CNT, S_YTD, S_REMOTE_CNT, S_DIST_01, S_DIST_02, S_DIST_03, S_DIST_04, S_DIST_05, S_DIST_06, S_DIST_07, S_DIST_08, S_DIST_09, S_DIST_10 FROM STOCK S, ITEM I WHERE I.I_ID = S.S_I_ID
@@ -0,0 +1,650 @@
+//! Loading with arrays of rows bound to prepared `INSERT` statements instead of formatting values into SQL text.
+//!
+//! Every table has a struct of [`Column`]s created with capacity of batch, rows are filled into columns and inserted
+//! by one execution once batch is full.
+//!
+//! CSV files are loaded in the same way, but every field is bound as text and converted by server.
+
+use std::{
+    fs::File,
+    io::{BufRead, BufReader},
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use anyhow::{anyhow, Context};
+use rsqlbench_core::tpcc::{
+    csv,
+    model::{
+        Customer, CustomerGenerator, District, DistrictGenerator, History, HistoryGenerator, Item,
+        ItemGenerator, NewOrder, Order, OrderGenerator, OrderLine, OrderLineGenerator, ScaleModel,
+        Stock, StockGenerator, Warehouse, DISTRICT_PER_WAREHOUSE,
+    },
+};
+use tracing::info;
+
+use crate::wrapper::{Batch, Binder, Column, Connection, Error, Text};
+
+/// Row of a table which could be filled into columns bound to an `INSERT` statement.
+trait Row {
+    type Columns;
+
+    /// `INSERT` statement with a placeholder for every column.
+    const INSERT: &'static str;
+
+    fn columns(capacity: usize) -> Self::Columns;
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error>;
+
+    fn fill(self, columns: &Self::Columns, row: usize);
+}
+
+/// Struct of columns with same capacity, which are bound to placeholders in order of fields.
+macro_rules! columns {
+    ($name:ident { $($field:ident: $ty:ty),* $(,)? }) => {
+        struct $name {
+            $($field: Column<$ty>,)*
+        }
+
+        impl $name {
+            fn new(capacity: usize) -> Self {
+                Self {
+                    $($field: Column::new(capacity),)*
+                }
+            }
+
+            fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self) -> Result<(), Error> {
+                let mut id = 0;
+                $(
+                    id += 1;
+                    binder.array(id, &mut columns.$field)?;
+                )*
+                Ok(())
+            }
+        }
+    };
+}
+
+columns!(ItemColumns {
+    id: u32,
+    image_id: u32,
+    name: Text<24>,
+    price: f32,
+    data: Text<50>,
+});
+
+impl Row for Item {
+    type Columns = ItemColumns;
+    const INSERT: &'static str = "INSERT INTO item (i_id, i_im_id, i_name, i_price, i_data) VALUES (?, ?, ?, ROUND(?, 2), ?)";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        ItemColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        ItemColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.id.set(row, self.id);
+        c.image_id.set(row, self.image_id as _);
+        c.name.set(row, self.name.as_str().into());
+        c.price.set(row, self.price);
+        c.data.set(row, self.data.as_str().into());
+    }
+}
+
+columns!(WarehouseColumns {
+    id: u32,
+    name: Text<10>,
+    street1: Text<20>,
+    street2: Text<20>,
+    city: Text<20>,
+    state: Text<2>,
+    zip: Text<9>,
+    tax: f32,
+    ytd: f32,
+});
+
+impl Row for Warehouse {
+    type Columns = WarehouseColumns;
+    const INSERT: &'static str = "INSERT INTO warehouse (w_id, w_name, w_street_1, w_street_2, w_city, w_state, w_zip, w_tax, w_ytd) VALUES (?, ?, ?, ?, ?, ?, ?, ROUND(?, 4), ROUND(?, 2))";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        WarehouseColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        WarehouseColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.id.set(row, self.id);
+        c.name.set(row, self.name.as_str().into());
+        c.street1.set(row, self.street.0.as_str().into());
+        c.street2.set(row, self.street.1.as_str().into());
+        c.city.set(row, self.city.as_str().into());
+        c.state.set(row, self.state.as_str().into());
+        c.zip.set(row, self.zip.as_str().into());
+        c.tax.set(row, self.tax);
+        c.ytd.set(row, self.ytd as _);
+    }
+}
+
+columns!(StockColumns {
+    item_id: u32,
+    warehouse_id: u32,
+    quantity: u32,
+    dist_01: Text<24>,
+    dist_02: Text<24>,
+    dist_03: Text<24>,
+    dist_04: Text<24>,
+    dist_05: Text<24>,
+    dist_06: Text<24>,
+    dist_07: Text<24>,
+    dist_08: Text<24>,
+    dist_09: Text<24>,
+    dist_10: Text<24>,
+    ytd: u32,
+    order_count: u32,
+    remote_count: u32,
+    data: Text<50>,
+});
+
+impl Row for Stock {
+    type Columns = StockColumns;
+    const INSERT: &'static str = "INSERT INTO stock (s_i_id, s_w_id, s_quantity, s_dist_01, s_dist_02, s_dist_03, s_dist_04, s_dist_05, s_dist_06, s_dist_07, s_dist_08, s_dist_09, s_dist_10, s_ytd, s_order_cnt, s_remote_cnt, s_data) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        StockColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        StockColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.item_id.set(row, self.item_id);
+        c.warehouse_id.set(row, self.warehouse_id);
+        c.quantity.set(row, self.quantity as _);
+        let dist = [
+            &c.dist_01, &c.dist_02, &c.dist_03, &c.dist_04, &c.dist_05, &c.dist_06, &c.dist_07,
+            &c.dist_08, &c.dist_09, &c.dist_10,
+        ];
+        for (column, value) in dist.into_iter().zip(&self.dist) {
+            column.set(row, value.as_str().into());
+        }
+        c.ytd.set(row, self.ytd);
+        c.order_count.set(row, self.order_count as _);
+        c.remote_count.set(row, self.remote_count as _);
+        c.data.set(row, self.data.as_str().into());
+    }
+}
+
+columns!(DistrictColumns {
+    id: u32,
+    warehouse_id: u32,
+    name: Text<10>,
+    street1: Text<20>,
+    street2: Text<20>,
+    city: Text<20>,
+    state: Text<2>,
+    zip: Text<9>,
+    tax: f32,
+    ytd: f32,
+    next_order_id: u32,
+});
+
+impl Row for District {
+    type Columns = DistrictColumns;
+    const INSERT: &'static str = "INSERT INTO district (d_id, d_w_id, d_name, d_street_1, d_street_2, d_city, d_state, d_zip, d_tax, d_ytd, d_next_o_id) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ROUND(?, 4), ROUND(?, 2), ?)";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        DistrictColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        DistrictColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.id.set(row, self.id as _);
+        c.warehouse_id.set(row, self.warehouse_id);
+        c.name.set(row, self.name.as_str().into());
+        c.street1.set(row, self.street.0.as_str().into());
+        c.street2.set(row, self.street.1.as_str().into());
+        c.city.set(row, self.city.as_str().into());
+        c.state.set(row, self.state.as_str().into());
+        c.zip.set(row, self.zip.as_str().into());
+        c.tax.set(row, self.tax);
+        c.ytd.set(row, self.ytd as _);
+        c.next_order_id.set(row, self.next_order_id);
+    }
+}
+
+columns!(CustomerColumns {
+    id: u32,
+    district_id: u32,
+    warehouse_id: u32,
+    first_name: Text<16>,
+    middle_name: Text<2>,
+    last_name: Text<16>,
+    street1: Text<20>,
+    street2: Text<20>,
+    city: Text<20>,
+    state: Text<2>,
+    zip: Text<9>,
+    phone: Text<16>,
+    credit: Text<2>,
+    credit_limit: f32,
+    discount: f32,
+    balance: f32,
+    ytd_payment: f32,
+    payment_count: u32,
+    delivery_count: u32,
+    data: Text<500>,
+});
+
+impl Row for Customer {
+    type Columns = CustomerColumns;
+    const INSERT: &'static str = "INSERT INTO customer (c_id, c_d_id, c_w_id, c_first, c_middle, c_last, c_street_1, c_street_2, c_city, c_state, c_zip, c_phone, c_since, c_credit, c_credit_lim, c_discount, c_balance, c_ytd_payment, c_payment_cnt, c_delivery_cnt, c_data) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, NOW(), ?, ROUND(?, 2), ROUND(?, 4), ROUND(?, 2), ROUND(?, 2), ?, ?, ?)";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        CustomerColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        CustomerColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.id.set(row, self.id as _);
+        c.district_id.set(row, self.district_id as _);
+        c.warehouse_id.set(row, self.warehouse_id);
+        c.first_name.set(row, self.first_name.as_str().into());
+        c.middle_name.set(row, self.middle_name.as_str().into());
+        c.last_name.set(row, self.last_name.as_str().into());
+        c.street1.set(row, self.street.0.as_str().into());
+        c.street2.set(row, self.street.1.as_str().into());
+        c.city.set(row, self.city.as_str().into());
+        c.state.set(row, self.state.as_str().into());
+        c.zip.set(row, self.zip.as_str().into());
+        c.phone.set(row, self.phone.as_str().into());
+        c.credit.set(row, self.credit.as_str().into());
+        c.credit_limit.set(row, self.credit_limit as _);
+        c.discount.set(row, self.discount);
+        c.balance.set(row, self.balance as _);
+        c.ytd_payment.set(row, self.ytd_payment as _);
+        c.payment_count.set(row, self.payment_count as _);
+        c.delivery_count.set(row, self.delivery_count as _);
+        c.data.set(row, self.data.as_str().into());
+    }
+}
+
+columns!(HistoryColumns {
+    customer_id: u32,
+    customer_district_id: u32,
+    customer_warehouse_id: u32,
+    district_id: u32,
+    warehouse_id: u32,
+    amount: f32,
+    data: Text<24>,
+});
+
+impl Row for History {
+    type Columns = HistoryColumns;
+    const INSERT: &'static str = "INSERT INTO history (h_c_id, h_c_d_id, h_c_w_id, h_d_id, h_w_id, h_date, h_amount, h_data) VALUES (?, ?, ?, ?, ?, NOW(), ROUND(?, 2), ?)";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        HistoryColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        HistoryColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.customer_id.set(row, self.customer_id as _);
+        c.customer_district_id
+            .set(row, self.customer_district_id as _);
+        c.customer_warehouse_id.set(row, self.customer_warehouse_id);
+        c.district_id.set(row, self.district_id as _);
+        c.warehouse_id.set(row, self.warehouse_id);
+        c.amount.set(row, self.amount);
+        c.data.set(row, self.data.as_str().into());
+    }
+}
+
+columns!(OrderColumns {
+    id: u32,
+    district_id: u32,
+    warehouse_id: u32,
+    customer_id: u32,
+    carrier_id: u32,
+    order_lines_count: u32,
+    all_local: u32,
+});
+
+impl Row for Order {
+    type Columns = OrderColumns;
+    const INSERT: &'static str = "INSERT INTO oorder (o_id, o_d_id, o_w_id, o_c_id, o_entry_d, o_carrier_id, o_ol_cnt, o_all_local) VALUES (?, ?, ?, ?, NOW(), ?, ?, ?)";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        OrderColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        OrderColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.id.set(row, self.id);
+        c.district_id.set(row, self.district_id as _);
+        c.warehouse_id.set(row, self.warehouse_id);
+        c.customer_id.set(row, self.customer_id as _);
+        match self.carrier_id {
+            Some(carrier_id) => c.carrier_id.set(row, carrier_id as _),
+            None => c.carrier_id.set_null(row),
+        }
+        c.order_lines_count.set(row, self.order_lines_count as _);
+        c.all_local.set(row, self.all_local as _);
+    }
+}
+
+columns!(NewOrderColumns {
+    order_id: u32,
+    district_id: u32,
+    warehouse_id: u32,
+});
+
+impl Row for NewOrder {
+    type Columns = NewOrderColumns;
+    const INSERT: &'static str =
+        "INSERT INTO new_order (no_o_id, no_d_id, no_w_id) VALUES (?, ?, ?)";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        NewOrderColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        NewOrderColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.order_id.set(row, self.order_id);
+        c.district_id.set(row, self.district_id as _);
+        c.warehouse_id.set(row, self.warehouse_id);
+    }
+}
+
+columns!(OrderLineColumns {
+    order_id: u32,
+    district_id: u32,
+    warehouse_id: u32,
+    number: u32,
+    item_id: u32,
+    supply_warehouse_id: u32,
+    delivered: u32,
+    quantity: u32,
+    amount: f32,
+    dist_info: Text<24>,
+});
+
+impl Row for OrderLine {
+    type Columns = OrderLineColumns;
+    // Delivery date is not bound as dates are opaque now, lines of delivered orders are delivered at loading.
+    const INSERT: &'static str = "INSERT INTO order_line (ol_o_id, ol_d_id, ol_w_id, ol_number, ol_i_id, ol_supply_w_id, ol_delivery_d, ol_quantity, ol_amount, ol_dist_info) VALUES (?, ?, ?, ?, ?, ?, CASE WHEN ? = 1 THEN NOW() END, ?, ROUND(?, 2), ?)";
+
+    fn columns(capacity: usize) -> Self::Columns {
+        OrderLineColumns::new(capacity)
+    }
+
+    fn bind<'p>(binder: &mut Binder<'p>, columns: &'p mut Self::Columns) -> Result<(), Error> {
+        OrderLineColumns::bind(binder, columns)
+    }
+
+    fn fill(self, c: &Self::Columns, row: usize) {
+        c.order_id.set(row, self.order_id);
+        c.district_id.set(row, self.district_id as _);
+        c.warehouse_id.set(row, self.warehouse_id);
+        c.number.set(row, self.number as _);
+        c.item_id.set(row, self.item_id);
+        c.supply_warehouse_id.set(row, self.supply_warehouse_id);
+        c.delivered.set(row, self.delivery_date.is_some() as _);
+        c.quantity.set(row, self.quantity as _);
+        c.amount.set(row, self.amount);
+        c.dist_info.set(row, self.dist_info.as_str().into());
+    }
+}
+
+fn batch<R: Row>(
+    conn: &Arc<Mutex<Connection>>,
+    capacity: usize,
+) -> Result<Batch<R::Columns>, Error> {
+    Batch::new(
+        conn.clone(),
+        R::INSERT,
+        capacity,
+        R::columns(capacity),
+        R::bind,
+    )
+}
+
+fn push<R: Row>(batch: &mut Batch<R::Columns>, row: R) -> Result<(), Error> {
+    batch.push(|columns, i| row.fill(columns, i))
+}
+
+fn insert_all<R: Row>(
+    conn: &Arc<Mutex<Connection>>,
+    capacity: usize,
+    rows: impl IntoIterator<Item = R>,
+) -> Result<(), Error> {
+    let mut batch = batch::<R>(conn, capacity)?;
+    for row in rows {
+        push(&mut batch, row)?;
+    }
+    batch.flush()
+}
+
+/// Insert items, which blocks until inserted.
+pub fn load_items(
+    conn: &Arc<Mutex<Connection>>,
+    generator: ItemGenerator,
+    batch_size: usize,
+) -> Result<(), Error> {
+    info!("Loading items by arrays (batch size={batch_size})");
+    insert_all(conn, batch_size, generator)
+}
+
+/// Insert warehouse with its stocks, districts, customers, histories and orders, which blocks until inserted.
+pub fn load_warehouse(
+    conn: &Arc<Mutex<Connection>>,
+    warehouse: &Warehouse,
+    scale: &ScaleModel,
+    batch_size: usize,
+) -> Result<(), Error> {
+    info!(
+        "Loading warehouse ID={id} by arrays (batch size={batch_size})",
+        id = warehouse.id
+    );
+    insert_all(conn, 1, [warehouse.clone()])?;
+    insert_all(
+        conn,
+        batch_size,
+        StockGenerator::from_warehouse(warehouse, scale),
+    )?;
+
+    let districts = DistrictGenerator::from_warehouse(warehouse, scale).collect::<Vec<_>>();
+    let mut customers = batch::<Customer>(conn, batch_size)?;
+    let mut histories = batch::<History>(conn, batch_size)?;
+    let mut orders = batch::<Order>(conn, batch_size)?;
+    let mut new_orders = batch::<NewOrder>(conn, batch_size)?;
+    let mut order_lines = batch::<OrderLine>(conn, batch_size)?;
+    for district in &districts {
+        for customer in CustomerGenerator::from_district(district, scale) {
+            for history in HistoryGenerator::from_customer(&customer) {
+                push(&mut histories, history)?;
+            }
+            push(&mut customers, customer)?;
+        }
+        for (order, new_order) in OrderGenerator::from_district(district, scale) {
+            for order_line in OrderLineGenerator::from_order(&order, scale) {
+                push(&mut order_lines, order_line)?;
+            }
+            if let Some(new_order) = new_order {
+                push(&mut new_orders, new_order)?;
+            }
+            push(&mut orders, order)?;
+        }
+    }
+    customers.flush()?;
+    histories.flush()?;
+    orders.flush()?;
+    new_orders.flush()?;
+    order_lines.flush()?;
+    insert_all(conn, DISTRICT_PER_WAREHOUSE, districts)
+}
+
+/// Buffer of every CSV field, which is as long as the longest column `c_data`.
+type CsvField = Text<500>;
+
+/// Columns written as `DATE` in CSV files.
+const CSV_DATE_COLUMNS: [&str; 4] = ["c_since", "h_date", "o_entry_d", "ol_delivery_d"];
+
+/// `INSERT` statement with a placeholder for every CSV field, dates are converted by `TO_DATE`.
+fn csv_insert(table: &str, columns: &str) -> String {
+    let values = columns
+        .split(", ")
+        .map(|column| match CSV_DATE_COLUMNS.contains(&column) {
+            true => "TO_DATE(?, 'YYYY-MM-DD HH24:MI:SS')",
+            false => "?",
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!("INSERT INTO {table} ({columns}) VALUES ({values})")
+}
+
+/// Batch of every field of CSV file bound as text.
+type CsvBatch = Batch<Vec<Column<CsvField>>>;
+
+fn csv_batch(
+    conn: &Arc<Mutex<Connection>>,
+    table: &str,
+    columns: &str,
+    capacity: usize,
+) -> Result<CsvBatch, Error> {
+    Batch::new(
+        conn.clone(),
+        &csv_insert(table, columns),
+        capacity,
+        columns.split(", ").map(|_| Column::new(capacity)).collect(),
+        |binder, columns| {
+            for (id, column) in (1..).zip(columns.iter_mut()) {
+                binder.array(id, column)?;
+            }
+            Ok(())
+        },
+    )
+}
+
+/// Fill fields of `line` into next row, `\N` is `NULL`.
+fn push_csv(batch: &mut CsvBatch, line: &str, count: usize) -> anyhow::Result<()> {
+    let fields = line.split(',').collect::<Vec<_>>();
+    if fields.len() != count {
+        return Err(anyhow!("{} fields, {count} expected", fields.len()));
+    }
+    batch.push(|columns, row| {
+        for (column, field) in columns.iter().zip(fields) {
+            match field {
+                "\\N" => column.set_null(row),
+                field => column.set(row, field.into()),
+            }
+        }
+    })?;
+    Ok(())
+}
+
+/// Insert rows of CSV file written by [`csv`], which blocks until inserted.
+pub fn load_csv(
+    conn: &Arc<Mutex<Connection>>,
+    table: &str,
+    path: &Path,
+    batch_size: usize,
+) -> anyhow::Result<()> {
+    let columns = csv::columns(table).ok_or_else(|| anyhow!("Unknown table {table}"))?;
+    let count = columns.split(", ").count();
+    info!(
+        table,
+        ?path,
+        "Loading CSV file by arrays (batch size={batch_size})"
+    );
+    let mut batch = csv_batch(conn, table, columns, batch_size)?;
+    let file = File::open(path).with_context(|| format!("Could not open {path:?}"))?;
+    for (number, line) in BufReader::new(file).lines().enumerate() {
+        let line = line.with_context(|| format!("Could not read {path:?}"))?;
+        push_csv(&mut batch, &line, count)
+            .with_context(|| format!("Invalid line {} of {path:?}", number + 1))?;
+    }
+    batch.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use rsqlbench_core::tpcc::csv;
+
+    use crate::{
+        native::mock::{mock, Call},
+        wrapper::Connection,
+    };
+
+    use super::{csv_batch, load_csv, push_csv, CsvField};
+
+    fn connect() -> Arc<Mutex<Connection>> {
+        Arc::new(Mutex::new(
+            Connection::connect("127.0.0.1:1688", "sys", "yasdb").unwrap(),
+        ))
+    }
+
+    #[test]
+    fn csv_fields() {
+        let mock = mock();
+        let columns = csv::columns("oorder").unwrap();
+        let mut batch = csv_batch(&connect(), "oorder", columns, 2).unwrap();
+        assert!(mock.calls().contains(&Call::Prepare(
+            "INSERT INTO oorder (o_id, o_d_id, o_w_id, o_c_id, o_entry_d, o_carrier_id, o_ol_cnt, o_all_local) VALUES (?, ?, ?, ?, TO_DATE(?, 'YYYY-MM-DD HH24:MI:SS'), ?, ?, ?)".to_string()
+        )));
+        assert!(push_csv(&mut batch, "1,1,1", 8).is_err());
+        push_csv(&mut batch, "1,1,1,7,2024-01-01 00:00:00,\\N,5,1", 8).unwrap();
+        push_csv(&mut batch, "2,1,1,8,2024-01-01 00:00:01,3,10,0", 8).unwrap();
+
+        let field = |id, row| unsafe { mock.input::<CsvField>(id, row) }.map(|f| f.to_string());
+        assert_eq!(field(4, 1).as_deref(), Some("8"));
+        assert_eq!(field(5, 0).as_deref(), Some("2024-01-01 00:00:00"));
+        assert_eq!(field(6, 0), None);
+        assert_eq!(field(6, 1).as_deref(), Some("3"));
+    }
+
+    #[test]
+    fn csv_file() {
+        let mock = mock();
+        let dir = std::env::temp_dir().join(format!("rsqlbench-yasdb-csv-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("new_order.1.csv");
+        std::fs::write(&path, "2101,1,1\n2102,1,1\n2103,1,1\n").unwrap();
+        load_csv(&connect(), "new_order", &path, 2).unwrap();
+        let executions = mock
+            .calls()
+            .into_iter()
+            .filter(|c| *c == Call::Execute)
+            .count();
+        assert_eq!(executions, 2);
+
+        std::fs::write(&path, "2101,1\n").unwrap();
+        assert!(load_csv(&connect(), "new_order", &path, 2).is_err());
+        assert!(load_csv(&connect(), "unknown", &path, 2).is_err());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
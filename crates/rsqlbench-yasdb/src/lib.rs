@@ -1,17 +1,12 @@
+mod bulk;
 mod loader;
 pub(crate) mod native;
 mod sut;
 mod terminal;
+mod worker;
 pub(crate) mod wrapper;
 
 pub(crate) use loader::*;
 pub use sut::*;
 pub(crate) use terminal::*;
-use wrapper::{DbcHandle, EnvHandle};
-
-pub(crate) struct Connection {
-    conn_handle: DbcHandle,
-    _env_handle: EnvHandle, // must be dropped after `conn_handle`
-}
-
-unsafe impl Send for Connection {}
+pub(crate) use worker::*;
@@ -1,40 +1,83 @@
 use std::{
+    path::Path,
     sync::{Arc, Mutex},
-    time::Duration,
+    time::{Duration, Instant},
 };
 
-use rsqlbench_core::tpcc::{
-    self,
-    loader::Loader,
-    model::{ItemGenerator, Warehouse},
+use rsqlbench_core::{
+    cfg::LoadMethod,
+    tpcc::{
+        self,
+        loader::Loader,
+        model::{ItemGenerator, ScaleModel, Warehouse},
+    },
 };
-use tokio::time::sleep;
+use tokio::{task::spawn_blocking, time::sleep};
 use tracing::info;
 
-use crate::{wrapper::SimpleExecutor, Connection};
+use crate::{
+    bulk,
+    wrapper::{Connection, SimpleExecutor},
+};
 
 pub struct YasdbLoader {
     conn: Arc<Mutex<Connection>>,
+    scale: ScaleModel,
+    method: LoadMethod,
+    batch_size: usize,
 }
 
 impl YasdbLoader {
-    pub fn new(conn: Connection) -> Self {
+    pub fn new(conn: Connection, scale: ScaleModel, method: LoadMethod, batch_size: usize) -> Self {
         Self {
             conn: Arc::new(Mutex::new(conn)),
+            scale,
+            method,
+            batch_size,
+        }
+    }
+
+    async fn load_warehouse(&self, warehouse: Warehouse) -> anyhow::Result<()> {
+        match self.method {
+            LoadMethod::Direct => {
+                let mut stmt = SimpleExecutor::new(self.conn.clone())?;
+                tpcc::sut::generic_direct::load_warehouse(&warehouse, &self.scale, &mut stmt).await
+            }
+            LoadMethod::Prepared => {
+                let (conn, scale, batch_size) = (self.conn.clone(), self.scale, self.batch_size);
+                spawn_blocking(move || bulk::load_warehouse(&conn, &warehouse, &scale, batch_size))
+                    .await??;
+                Ok(())
+            }
         }
     }
+
+    /// Commit rows loaded by this connection.
+    async fn commit(&self) -> anyhow::Result<()> {
+        let conn = self.conn.clone();
+        spawn_blocking(move || conn.lock().unwrap().commit()).await??;
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
 impl Loader for YasdbLoader {
     async fn load_items(&mut self, generator: ItemGenerator) -> anyhow::Result<()> {
-        tpcc::sut::generic_direct::load_items(
-            generator,
-            5000,
-            &mut SimpleExecutor::new(self.conn.clone())?,
-        )
-        .await?;
-        Ok(())
+        match self.method {
+            LoadMethod::Direct => {
+                tpcc::sut::generic_direct::load_items(
+                    generator,
+                    5000,
+                    &mut SimpleExecutor::new(self.conn.clone())?,
+                )
+                .await?
+            }
+            LoadMethod::Prepared => {
+                let (conn, batch_size) = (self.conn.clone(), self.batch_size);
+                spawn_blocking(move || bulk::load_items(&conn, generator, batch_size)).await??
+            }
+        }
+        self.commit().await
     }
 
     async fn load_warehouses(
@@ -42,11 +85,35 @@ impl Loader for YasdbLoader {
         generator: async_channel::Receiver<Warehouse>,
     ) -> anyhow::Result<()> {
         sleep(Duration::from_secs(1)).await;
-        let mut stmt = SimpleExecutor::new(self.conn.clone())?;
         while let Ok(warehouse) = generator.recv().await {
-            info!("Loading warehouse ID={id}", id = warehouse.id);
-            tpcc::sut::generic_direct::load_warehouse(&warehouse, &mut stmt).await?;
+            let id = warehouse.id;
+            info!("Loading warehouse ID={id}");
+            let begin = Instant::now();
+            if let Err(e) = self.load_warehouse(warehouse).await {
+                let conn = self.conn.clone();
+                spawn_blocking(move || conn.lock().unwrap().rollback()).await??;
+                return Err(e);
+            }
+            self.commit().await?;
+            let method = self.method;
+            info!(id, ?method, elapsed = ?begin.elapsed(), "Warehouse loaded");
+        }
+        Ok(())
+    }
+
+    async fn load_csv(&mut self, table: &str, path: &Path) -> anyhow::Result<()> {
+        let (conn, batch_size) = (self.conn.clone(), self.batch_size);
+        let (table, path) = (table.to_string(), path.to_path_buf());
+        let begin = Instant::now();
+        if let Err(e) =
+            spawn_blocking(move || bulk::load_csv(&conn, &table, &path, batch_size)).await?
+        {
+            let conn = self.conn.clone();
+            spawn_blocking(move || conn.lock().unwrap().rollback()).await??;
+            return Err(e);
         }
+        self.commit().await?;
+        info!(elapsed = ?begin.elapsed(), "CSV file loaded");
         Ok(())
     }
 }
@@ -0,0 +1,156 @@
+//! Functions of the subset of `yacli.h` used by this crate, which are resolved from `libyascli` loaded by [`load`] at
+//! runtime, so that neither headers nor libraries are required at build time.
+//!
+//! Declarations are checked against bindings generated from `yacli.h` when it's present at build time.
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(non_snake_case)]
+#![allow(clippy::upper_case_acronyms)]
+#![allow(clippy::missing_safety_doc)]
+#![allow(clippy::too_many_arguments)]
+
+use std::{
+    os::raw::{c_char, c_void},
+    sync::OnceLock,
+};
+
+use libloading::Library;
+
+use super::types::*;
+
+/// Declare functions, which are resolved by [`Api::load`] and called through the loaded [`Api`].
+macro_rules! functions {
+    ($(pub fn $name:ident($($arg:ident: $ty:ty),* $(,)?) -> $ret:ty;)*) => {
+        struct Api {
+            $($name: unsafe extern "C" fn($($ty),*) -> $ret,)*
+            _library: Library, // must be dropped after functions are not used
+        }
+
+        impl Api {
+            unsafe fn load(library: &str) -> Result<Self, libloading::Error> {
+                let library = Library::new(library)?;
+                Ok(Self {
+                    $($name: *library.get(concat!(stringify!($name), "\0").as_bytes())?,)*
+                    _library: library,
+                })
+            }
+        }
+
+        $(
+            pub unsafe fn $name($($arg: $ty),*) -> $ret {
+                (api().$name)($($arg),*)
+            }
+        )*
+
+        /// Functions of generated bindings have the same signatures as declared ones, it's generic so that
+        /// unlinked functions are never referenced by generated code.
+        #[cfg(yacli_header)]
+        #[allow(dead_code)]
+        fn same_signatures<T>() {
+            $(let _: unsafe extern "C" fn($($ty),*) -> $ret = generated::$name;)*
+        }
+    };
+}
+
+functions! {
+    pub fn yacAllocHandle(type_: EnYacHandleType, input: YacHandle, output: *mut YacHandle) -> EnYacResult;
+    pub fn yacFreeHandle(type_: EnYacHandleType, handle: YacHandle) -> EnYacResult;
+    pub fn yacSetEnvAttr(handle: YacHandle, attr: EnYacEnvAttr, value: *mut c_void, length: YacInt32) -> EnYacResult;
+    pub fn yacConnect(
+        handle: YacHandle,
+        url: *mut c_char,
+        url_length: YacInt16,
+        user: *mut c_char,
+        user_length: YacInt16,
+        password: *mut c_char,
+        password_length: YacInt16,
+    ) -> EnYacResult;
+    pub fn yacCommit(handle: YacHandle) -> EnYacResult;
+    pub fn yacRollback(handle: YacHandle) -> EnYacResult;
+    pub fn yacPrepare(handle: YacHandle, sql: *mut c_char, length: YacInt32) -> EnYacResult;
+    pub fn yacExecute(handle: YacHandle) -> EnYacResult;
+    pub fn yacDirectExecute(handle: YacHandle, sql: *mut c_char, length: YacInt32) -> EnYacResult;
+    pub fn yacSetStmtAttr(handle: YacHandle, attr: EnYacStmtAttr, value: *mut c_void, length: YacInt32) -> EnYacResult;
+    pub fn yacBindParameter(
+        handle: YacHandle,
+        id: YacUint16,
+        direction: YacParamDirection,
+        type_: YacUint32,
+        value: *mut c_void,
+        buffer_length: YacInt32,
+        size: YacInt32,
+        indicator: *mut YacInt32,
+    ) -> EnYacResult;
+    pub fn yacBindColumn(
+        handle: YacHandle,
+        id: YacUint16,
+        type_: YacUint32,
+        value: *mut c_void,
+        buffer_length: YacInt32,
+        indicator: *mut YacInt32,
+    ) -> EnYacResult;
+    pub fn yacFetch(handle: YacHandle, rows: *mut YacUint32) -> EnYacResult;
+    pub fn yacGetDiagRec(
+        code: *mut YacInt32,
+        message: *mut c_char,
+        length: YacInt32,
+        message_length: *mut YacInt32,
+        sql_state: *mut c_char,
+        sql_state_length: YacInt32,
+        pos: *mut YacTextPos,
+    ) -> EnYacResult;
+}
+
+static API: OnceLock<Api> = OnceLock::new();
+
+/// Load `libyascli` by file name(searched like `dlopen`) or path, only the first loaded library is used.
+pub fn load(library: &str) -> Result<(), libloading::Error> {
+    if API.get().is_none() {
+        let api = unsafe { Api::load(library)? };
+        let _ = API.set(api);
+    }
+    Ok(())
+}
+
+fn api() -> &'static Api {
+    API.get()
+        .expect("yascli should be loaded before connecting to YashanDB")
+}
+
+#[cfg(yacli_header)]
+mod generated {
+    #![allow(non_upper_case_globals)]
+    #![allow(non_camel_case_types)]
+    #![allow(non_snake_case)]
+    #![allow(improper_ctypes)]
+    #![allow(unused)]
+    #![allow(clippy::upper_case_acronyms)]
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+
+/// Declared constants are the same as generated ones.
+#[cfg(yacli_header)]
+const _: () = {
+    macro_rules! same {
+        ($($name:ident),* $(,)?) => {
+            $(assert!($name == generated::$name);)*
+        };
+    }
+    same!(
+        EnYacResult_YAC_ERROR,
+        EnYacHandleType_YAC_HANDLE_ENV,
+        EnYacHandleType_YAC_HANDLE_DBC,
+        EnYacHandleType_YAC_HANDLE_STMT,
+        EnYacEnvAttr_YAC_ATTR_CHARSET_CODE,
+        EnYacCharsetCode_YAC_CHARSET_UTF8,
+        EnYacStmtAttr_YAC_ATTR_PARAMSET_SIZE,
+        EnYacParamDirection_YAC_PARAM_INPUT,
+        EnYacParamDirection_YAC_PARAM_OUTPUT,
+        EnYacExtType_YAC_SQLT_INTEGER,
+        EnYacExtType_YAC_SQLT_FLOAT,
+        EnYacExtType_YAC_SQLT_VARCHAR2,
+        EnYacExtType_YAC_SQLT_DATE,
+    );
+    assert!(std::mem::size_of::<YacTextPos>() == std::mem::size_of::<generated::YacTextPos>());
+};
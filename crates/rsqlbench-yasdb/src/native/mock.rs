@@ -0,0 +1,410 @@
+//! Fake yascli for unit tests, which records calls and returns scripted results instead of calling YashanDB.
+//!
+//! Calls are made on blocking threads, so state is shared by all threads, and tests faking yascli are serialized by
+//! holding [`Mock`] returned by [`mock`].
+
+#![allow(non_snake_case)]
+#![allow(clippy::missing_safety_doc)]
+#![allow(clippy::too_many_arguments)]
+
+use std::{
+    collections::VecDeque,
+    convert::Infallible,
+    mem::size_of,
+    os::raw::{c_char, c_void},
+    ptr::copy_nonoverlapping,
+    slice,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use super::types::*;
+
+const SUCCESS: EnYacResult = 0;
+
+/// Indicator of `NULL` value.
+const NULL_DATA: YacInt32 = -1;
+
+/// Call of faked function, handles are omitted.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Call {
+    AllocHandle(EnYacHandleType),
+    FreeHandle(EnYacHandleType),
+    SetEnvAttr(EnYacEnvAttr),
+    Connect {
+        url: String,
+        user: String,
+        password: String,
+    },
+    Commit,
+    Rollback,
+    Prepare(String),
+    Execute,
+    DirectExecute(String),
+    SetStmtAttr(EnYacStmtAttr, u32),
+    BindParameter {
+        id: YacUint16,
+        direction: YacParamDirection,
+        type_: YacUint32,
+    },
+    BindColumn {
+        id: YacUint16,
+        type_: YacUint32,
+    },
+    Fetch,
+}
+
+/// Diagnostics returned by `yacGetDiagRec` after a failed call.
+#[derive(Debug, Clone)]
+pub struct Diag {
+    pub code: YacInt32,
+    pub message: String,
+    pub sql_state: String,
+    pub pos: (YacInt32, YacInt32),
+}
+
+impl Diag {
+    /// Diagnostics without SQL state or position.
+    pub fn new(code: YacInt32, message: &str) -> Self {
+        Self {
+            code,
+            message: message.to_string(),
+            sql_state: String::new(),
+            pos: (-1, -1),
+        }
+    }
+}
+
+/// Buffer bound to parameter or column of statement, pointers are stored as addresses so that state is `Send`.
+struct Binding {
+    stmt: usize,
+    id: YacUint16,
+    value: usize,
+    buffer_length: YacInt32,
+    indicator: usize,
+}
+
+struct State {
+    next_handle: usize,
+    calls: Vec<Call>,
+    failures: Vec<(&'static str, Diag)>,
+    diag: Option<Diag>,
+    executed: usize,
+    outputs: Vec<(YacUint16, Vec<u8>)>,
+    rows: VecDeque<Vec<(YacUint16, Vec<u8>)>>,
+    parameters: Vec<Binding>,
+    columns: Vec<Binding>,
+}
+
+impl State {
+    const fn new() -> Self {
+        Self {
+            next_handle: 0,
+            calls: Vec::new(),
+            failures: Vec::new(),
+            diag: None,
+            executed: 0,
+            outputs: Vec::new(),
+            rows: VecDeque::new(),
+            parameters: Vec::new(),
+            columns: Vec::new(),
+        }
+    }
+
+    /// Record call, which fails if a failure of `function` is scripted.
+    fn call(&mut self, function: &str, call: Call) -> EnYacResult {
+        self.calls.push(call);
+        match self.failures.iter().position(|(f, _)| *f == function) {
+            Some(i) => {
+                self.diag = Some(self.failures.remove(i).1);
+                EnYacResult_YAC_ERROR
+            }
+            None => SUCCESS,
+        }
+    }
+}
+
+static STATE: Mutex<State> = Mutex::new(State::new());
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn state() -> MutexGuard<'static, State> {
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Faked yascli held by a test, see [`mock`].
+pub struct Mock {
+    _serial: MutexGuard<'static, ()>,
+}
+
+/// Reset faked yascli and hold it until returned [`Mock`] is dropped.
+pub fn mock() -> Mock {
+    let serial = SERIAL.lock().unwrap_or_else(PoisonError::into_inner);
+    *state() = State::new();
+    Mock { _serial: serial }
+}
+
+impl Mock {
+    /// Fail the next call of `function` with diagnostics `diag`.
+    pub fn fail(&self, function: &'static str, diag: Diag) {
+        state().failures.push((function, diag));
+    }
+
+    /// Diagnostics returned by `yacGetDiagRec`, `None` if there is no error.
+    pub fn set_diag(&self, diag: Option<Diag>) {
+        state().diag = diag;
+    }
+
+    /// Write `value` into parameter `id` on the next successful execution.
+    pub fn output<T: Copy>(&self, id: YacUint16, value: T) {
+        state().outputs.push((id, bytes(&value)));
+    }
+
+    /// Fetch `values` as rows of column `id`.
+    pub fn rows<T: Copy>(&self, id: YacUint16, values: &[T]) {
+        let mut state = state();
+        for (i, value) in values.iter().enumerate() {
+            if state.rows.len() <= i {
+                state.rows.push_back(vec![]);
+            }
+            state.rows[i].push((id, bytes(value)));
+        }
+    }
+
+    /// Calls recorded in order.
+    pub fn calls(&self) -> Vec<Call> {
+        state().calls.clone()
+    }
+
+    /// Value of `row` of parameter `id` of the last executed statement, `None` if it's `NULL`.
+    ///
+    /// # Safety
+    ///
+    /// Buffer bound to the parameter must be alive and hold values of `T`.
+    pub unsafe fn input<T: Copy>(&self, id: YacUint16, row: usize) -> Option<T> {
+        let state = state();
+        let binding = state
+            .parameters
+            .iter()
+            .rev()
+            .find(|b| b.stmt == state.executed && b.id == id)
+            .expect("parameter should be bound");
+        let indicator = binding.indicator as *const YacInt32;
+        if !indicator.is_null() && *indicator.add(row) == NULL_DATA {
+            return None;
+        }
+        let value = (binding.value as *const u8).add(row * binding.buffer_length as usize);
+        Some((value as *const T).read_unaligned())
+    }
+}
+
+fn bytes<T: Copy>(value: &T) -> Vec<u8> {
+    unsafe { slice::from_raw_parts(value as *const T as *const u8, size_of::<T>()) }.to_vec()
+}
+
+unsafe fn text(value: *const c_char, length: usize) -> String {
+    String::from_utf8_lossy(slice::from_raw_parts(value as *const u8, length)).into_owned()
+}
+
+/// Copy `text` into buffer of `length` bytes, which is truncated and terminated by `\0`.
+unsafe fn copy_text(text: &str, buffer: *mut c_char, length: YacInt32) {
+    let len = text.len().min(length as usize - 1);
+    copy_nonoverlapping(text.as_ptr(), buffer as *mut u8, len);
+    *buffer.add(len) = 0;
+}
+
+/// Copy scripted values into buffers bound to `stmt`, which are truncated to lengths of buffers.
+unsafe fn write(bindings: &[Binding], stmt: YacHandle, values: Vec<(YacUint16, Vec<u8>)>) {
+    for (id, value) in values {
+        let binding = bindings
+            .iter()
+            .rev()
+            .find(|b| b.stmt == stmt as usize && b.id == id)
+            .expect("scripted value should be bound");
+        let len = value.len().min(binding.buffer_length as usize);
+        copy_nonoverlapping(value.as_ptr(), binding.value as *mut u8, len);
+    }
+}
+
+/// Library is faked, nothing to load.
+pub fn load(_library: &str) -> Result<(), Infallible> {
+    Ok(())
+}
+
+pub unsafe fn yacAllocHandle(
+    type_: EnYacHandleType,
+    _input: YacHandle,
+    output: *mut YacHandle,
+) -> EnYacResult {
+    let mut state = state();
+    let result = state.call("yacAllocHandle", Call::AllocHandle(type_));
+    if result == SUCCESS {
+        state.next_handle += 1;
+        *output = state.next_handle as YacHandle;
+    }
+    result
+}
+
+pub unsafe fn yacFreeHandle(type_: EnYacHandleType, _handle: YacHandle) -> EnYacResult {
+    state().call("yacFreeHandle", Call::FreeHandle(type_))
+}
+
+pub unsafe fn yacSetEnvAttr(
+    _handle: YacHandle,
+    attr: EnYacEnvAttr,
+    _value: *mut c_void,
+    _length: YacInt32,
+) -> EnYacResult {
+    state().call("yacSetEnvAttr", Call::SetEnvAttr(attr))
+}
+
+pub unsafe fn yacConnect(
+    _handle: YacHandle,
+    url: *mut c_char,
+    url_length: YacInt16,
+    user: *mut c_char,
+    user_length: YacInt16,
+    password: *mut c_char,
+    password_length: YacInt16,
+) -> EnYacResult {
+    let call = Call::Connect {
+        url: text(url, url_length as _),
+        user: text(user, user_length as _),
+        password: text(password, password_length as _),
+    };
+    state().call("yacConnect", call)
+}
+
+pub unsafe fn yacCommit(_handle: YacHandle) -> EnYacResult {
+    state().call("yacCommit", Call::Commit)
+}
+
+pub unsafe fn yacRollback(_handle: YacHandle) -> EnYacResult {
+    state().call("yacRollback", Call::Rollback)
+}
+
+pub unsafe fn yacPrepare(_handle: YacHandle, sql: *mut c_char, length: YacInt32) -> EnYacResult {
+    state().call("yacPrepare", Call::Prepare(text(sql, length as _)))
+}
+
+pub unsafe fn yacExecute(handle: YacHandle) -> EnYacResult {
+    let mut state = state();
+    let result = state.call("yacExecute", Call::Execute);
+    state.executed = handle as _;
+    if result == SUCCESS {
+        let outputs = std::mem::take(&mut state.outputs);
+        write(&state.parameters, handle, outputs);
+    }
+    result
+}
+
+pub unsafe fn yacDirectExecute(
+    _handle: YacHandle,
+    sql: *mut c_char,
+    length: YacInt32,
+) -> EnYacResult {
+    state().call(
+        "yacDirectExecute",
+        Call::DirectExecute(text(sql, length as _)),
+    )
+}
+
+pub unsafe fn yacSetStmtAttr(
+    _handle: YacHandle,
+    attr: EnYacStmtAttr,
+    value: *mut c_void,
+    _length: YacInt32,
+) -> EnYacResult {
+    let call = Call::SetStmtAttr(attr, *(value as *const u32));
+    state().call("yacSetStmtAttr", call)
+}
+
+pub unsafe fn yacBindParameter(
+    handle: YacHandle,
+    id: YacUint16,
+    direction: YacParamDirection,
+    type_: YacUint32,
+    value: *mut c_void,
+    buffer_length: YacInt32,
+    _size: YacInt32,
+    indicator: *mut YacInt32,
+) -> EnYacResult {
+    let mut state = state();
+    let call = Call::BindParameter {
+        id,
+        direction,
+        type_,
+    };
+    let result = state.call("yacBindParameter", call);
+    if result == SUCCESS {
+        state.parameters.push(Binding {
+            stmt: handle as _,
+            id,
+            value: value as _,
+            buffer_length,
+            indicator: indicator as _,
+        });
+    }
+    result
+}
+
+pub unsafe fn yacBindColumn(
+    handle: YacHandle,
+    id: YacUint16,
+    type_: YacUint32,
+    value: *mut c_void,
+    buffer_length: YacInt32,
+    indicator: *mut YacInt32,
+) -> EnYacResult {
+    let mut state = state();
+    let result = state.call("yacBindColumn", Call::BindColumn { id, type_ });
+    if result == SUCCESS {
+        state.columns.push(Binding {
+            stmt: handle as _,
+            id,
+            value: value as _,
+            buffer_length,
+            indicator: indicator as _,
+        });
+    }
+    result
+}
+
+pub unsafe fn yacFetch(handle: YacHandle, rows: *mut YacUint32) -> EnYacResult {
+    let mut state = state();
+    let result = state.call("yacFetch", Call::Fetch);
+    if result == SUCCESS {
+        *rows = match state.rows.pop_front() {
+            Some(row) => {
+                write(&state.columns, handle, row);
+                1
+            }
+            None => 0,
+        };
+    }
+    result
+}
+
+pub unsafe fn yacGetDiagRec(
+    code: *mut YacInt32,
+    message: *mut c_char,
+    length: YacInt32,
+    message_length: *mut YacInt32,
+    sql_state: *mut c_char,
+    sql_state_length: YacInt32,
+    pos: *mut YacTextPos,
+) -> EnYacResult {
+    let state = state();
+    let Some(diag) = &state.diag else {
+        return EnYacResult_YAC_ERROR;
+    };
+    *code = diag.code;
+    copy_text(&diag.message, message, length);
+    if !message_length.is_null() {
+        *message_length = diag.message.len() as _;
+    }
+    copy_text(&diag.sql_state, sql_state, sql_state_length);
+    *pos = YacTextPos {
+        line: diag.pos.0,
+        column: diag.pos.1,
+    };
+    SUCCESS
+}
@@ -0,0 +1,46 @@
+//! Bindings of yascli, generated from `yacli.h` and linked at build time, or declared by hand and loaded at runtime
+//! with feature `dynamic`.
+//!
+//! Unit tests always use declared ones with functions faked by [`mock`], so that no YashanDB is required.
+
+#[cfg(not(any(feature = "dynamic", test)))]
+mod generated {
+    #![allow(non_upper_case_globals)]
+    #![allow(non_camel_case_types)]
+    #![allow(non_snake_case)]
+    #![allow(improper_ctypes)]
+    #![allow(unused)]
+    #![allow(clippy::upper_case_acronyms)]
+    include!(concat!(env!("OUT_DIR"), "/bindings.rs"));
+}
+
+#[cfg(not(any(feature = "dynamic", test)))]
+pub use generated::*;
+
+#[cfg(any(feature = "dynamic", test))]
+mod types;
+
+#[cfg(any(feature = "dynamic", test))]
+pub use types::*;
+
+#[cfg(all(feature = "dynamic", not(test)))]
+mod dynamic;
+
+#[cfg(all(feature = "dynamic", not(test)))]
+pub use dynamic::*;
+
+#[cfg(test)]
+pub mod mock;
+
+#[cfg(test)]
+pub use mock::{
+    load, yacAllocHandle, yacBindColumn, yacBindParameter, yacCommit, yacConnect, yacDirectExecute,
+    yacExecute, yacFetch, yacFreeHandle, yacGetDiagRec, yacPrepare, yacRollback, yacSetEnvAttr,
+    yacSetStmtAttr,
+};
+
+/// Library is linked at build time, nothing to load.
+#[cfg(not(any(feature = "dynamic", test)))]
+pub fn load(_library: &str) -> Result<(), std::convert::Infallible> {
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! Types and constants of the subset of `yacli.h` used by this crate, declared by hand so that bindings are not
+//! generated for feature `dynamic`.
+//!
+//! Constants are passed to `libyascli` as they are, they are asserted to be the same as `yacli.h` of the client
+//! downloaded by `scripts/download-yascli.sh`(23.2.1.100), which is required at build time unless `YACLI_UNCHECKED`
+//! is set for unit tests with fake driver.
+
+#![allow(non_upper_case_globals)]
+#![allow(non_camel_case_types)]
+#![allow(clippy::upper_case_acronyms)]
+
+use std::os::raw::{c_int, c_uint, c_void};
+
+pub type YacHandle = *mut c_void;
+pub type YacUint16 = u16;
+pub type YacInt16 = i16;
+pub type YacUint32 = u32;
+pub type YacInt32 = i32;
+
+pub type EnYacResult = c_int;
+pub const EnYacResult_YAC_ERROR: EnYacResult = -1;
+
+pub type EnYacHandleType = c_uint;
+pub const EnYacHandleType_YAC_HANDLE_ENV: EnYacHandleType = 1;
+pub const EnYacHandleType_YAC_HANDLE_DBC: EnYacHandleType = 2;
+pub const EnYacHandleType_YAC_HANDLE_STMT: EnYacHandleType = 3;
+
+pub type EnYacEnvAttr = c_uint;
+pub const EnYacEnvAttr_YAC_ATTR_CHARSET_CODE: EnYacEnvAttr = 1;
+
+pub type EnYacCharsetCode = c_uint;
+pub const EnYacCharsetCode_YAC_CHARSET_UTF8: EnYacCharsetCode = 1;
+
+pub type EnYacStmtAttr = c_uint;
+pub const EnYacStmtAttr_YAC_ATTR_PARAMSET_SIZE: EnYacStmtAttr = 1;
+
+pub type EnYacParamDirection = c_uint;
+pub type YacParamDirection = EnYacParamDirection;
+pub const EnYacParamDirection_YAC_PARAM_INPUT: EnYacParamDirection = 1;
+pub const EnYacParamDirection_YAC_PARAM_OUTPUT: EnYacParamDirection = 2;
+
+pub type EnYacExtType = c_uint;
+pub const EnYacExtType_YAC_SQLT_INTEGER: EnYacExtType = 1;
+pub const EnYacExtType_YAC_SQLT_FLOAT: EnYacExtType = 2;
+pub const EnYacExtType_YAC_SQLT_VARCHAR2: EnYacExtType = 3;
+pub const EnYacExtType_YAC_SQLT_DATE: EnYacExtType = 4;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct YacTextPos {
+    pub line: YacInt32,
+    pub column: YacInt32,
+}
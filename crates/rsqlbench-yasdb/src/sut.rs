@@ -1,139 +1,447 @@
 use std::sync::{Arc, Mutex};
 
+use anyhow::anyhow;
 use rsqlbench_core::{
-    cfg::Connection as ConnectionCfg,
+    cfg::{Connection as ConnectionCfg, ConnectionOptions, LoadMethod, Role, Schema},
     tpcc::{
+        check::{expected_rows, CheckError, CONSISTENCY_CONDITIONS},
         loader::Loader,
-        sut::{generic_direct::Executor, Sut, Terminal},
+        model::ScaleModel,
+        sut::{generic_direct::Executor, SqlFiles, Sut, Terminal},
     },
 };
-use tokio::task::spawn_blocking;
+use tokio::{task::spawn_blocking, time::timeout};
 use tracing::{info, warn};
 
 use crate::{
-    native::{yacConnect, EnYacResult_YAC_ERROR},
-    wrapper::{DbcHandle, EnvHandle, Error, SimpleExecutor},
-    Connection, YasdbLoader, YasdbTerminal,
+    native,
+    wrapper::{Connection, Prepared, SimpleExecutor},
+    Threads, Worker, YasdbLoader, YasdbTerminal,
 };
 
 pub struct YasdbSut {
     connection: ConnectionCfg,
     warehouse_count: u32,
+    scale: ScaleModel,
+    load_method: LoadMethod,
+    batch_size: usize,
+    schema: Schema,
+    sql: SqlFiles,
+
+    /// Pool shared by terminals, spawned when the first terminal is created.
+    pool: Mutex<Option<Worker>>,
+}
+
+/// Client library searched like `dlopen` if not configured.
+const DEFAULT_LIBRARY: &str = "libyascli.so";
+
+/// Tables with column of warehouse ID.
+const TABLES: [(&str, Option<&str>); 9] = [
+    ("customer", Some("C_W_ID")),
+    ("district", Some("D_W_ID")),
+    ("history", Some("H_W_ID")),
+    ("item", None),
+    ("warehouse", Some("W_ID")),
+    ("stock", Some("S_W_ID")),
+    ("new_order", Some("NO_W_ID")),
+    ("oorder", Some("O_W_ID")),
+    ("order_line", Some("OL_W_ID")),
+];
+
+const PROCEDURES: [&str; 5] = [
+    "new_order.sql",
+    "payment.sql",
+    "delivery.sql",
+    "order_status.sql",
+    "stock_level.sql",
+];
+
+/// Objects created by built-in SQL files, dropped in order if there is no dedicated schema.
+const OBJECTS: [(&str, &str); 15] = [
+    ("VIEW", "STOCK_ITEM"),
+    ("PROCEDURE", "NEWORD"),
+    ("PROCEDURE", "PAYMENT"),
+    ("PROCEDURE", "DELIVERY"),
+    ("PROCEDURE", "OSTAT"),
+    ("PROCEDURE", "SLEV"),
+    ("TABLE", "CUSTOMER"),
+    ("TABLE", "DISTRICT"),
+    ("TABLE", "HISTORY"),
+    ("TABLE", "ITEM"),
+    ("TABLE", "WAREHOUSE"),
+    ("TABLE", "STOCK"),
+    ("TABLE", "NEW_ORDER"),
+    ("TABLE", "OORDER"),
+    ("TABLE", "ORDER_LINE"),
+];
+
+/// Privileges granted to dedicated schema if `others.grants` is not configured.
+const DEFAULT_GRANTS: &str = "DBA";
+
+macro_rules! builtin_sql {
+    ($($name:literal),* $(,)?) => {
+        &[$(($name, include_str!(concat!("../sql/", $name)))),*]
+    };
 }
 
+static BUILTIN_SQL: &[(&str, &str)] = builtin_sql!(
+    "tables/customer.sql",
+    "tables/district.sql",
+    "tables/history.sql",
+    "tables/item.sql",
+    "tables/warehouse.sql",
+    "tables/stock.sql",
+    "tables/new_order.sql",
+    "tables/oorder.sql",
+    "tables/order_line.sql",
+    "indexes.sql",
+    "stock_item.sql",
+    "new_order.sql",
+    "payment.sql",
+    "delivery.sql",
+    "order_status.sql",
+    "stock_level.sql",
+);
+
 impl YasdbSut {
-    pub fn new(connection: ConnectionCfg, warehouse_count: u32) -> Self {
+    pub fn new(
+        connection: ConnectionCfg,
+        warehouse_count: u32,
+        scale: ScaleModel,
+        load_method: LoadMethod,
+        batch_size: usize,
+        schema: Schema,
+    ) -> Self {
+        let sql = SqlFiles::from_schema(
+            &schema,
+            &connection.database,
+            warehouse_count,
+            &scale,
+            BUILTIN_SQL,
+        );
         Self {
             connection,
             warehouse_count,
+            scale,
+            load_method,
+            batch_size,
+            schema,
+            sql,
+            pool: Mutex::new(None),
         }
     }
 
-    async fn connect(&self, url: String) -> anyhow::Result<Connection> {
-        let username = self.connection.connections.others["username"].clone();
-        let password = self.connection.connections.others["password"].clone();
-        let conn = spawn_blocking(move || {
-            let env_handle = EnvHandle::new()?.with_utf8();
-            let conn_handle = DbcHandle::new(&env_handle)?;
-            let result = unsafe {
-                yacConnect(
-                    conn_handle.0,
-                    url.as_ptr() as _,
-                    url.len() as _,
-                    username.as_ptr() as _,
-                    username.len() as _,
-                    password.as_ptr() as _,
-                    password.len() as _,
-                )
-            };
+    fn other(&self, key: &str) -> anyhow::Result<String> {
+        self.connection
+            .connections
+            .others
+            .get(key)
+            .cloned()
+            .ok_or_else(|| anyhow!("`connection.connections.others.{key}` is required by YashanDB"))
+    }
 
-            if result == EnYacResult_YAC_ERROR {
-                return Err(Error::get_yas_diag(None).unwrap());
+    fn threads(&self) -> anyhow::Result<Threads> {
+        match self.connection.connections.others.get("threads") {
+            Some(threads) => threads
+                .parse()
+                .map_err(|e| anyhow!("`connection.connections.others.threads`: {e}")),
+            None => Ok(Threads::default()),
+        }
+    }
+
+    fn worker(&self, id: u32) -> anyhow::Result<Worker> {
+        Ok(match self.threads()? {
+            Threads::Blocking => Worker::Blocking,
+            Threads::Dedicated => Worker::threads(&format!("yasdb-terminal-{id}"), 1)?,
+            Threads::Pool(size) => {
+                let mut pool = self.pool.lock().unwrap();
+                match &*pool {
+                    Some(worker) => worker.clone(),
+                    None => {
+                        info!(size, "Spawning threads for terminals");
+                        pool.insert(Worker::threads("yasdb-terminal", size)?)
+                            .clone()
+                    }
+                }
             }
-            Ok(Connection {
-                conn_handle,
-                _env_handle: env_handle,
-            })
         })
-        .await?;
-        Ok(conn?)
     }
+
+    /// User owning objects of benchmark, which is created by `others.username` from `connection.database` if
+    /// `others.dedicated_schema` is `true`, otherwise `None` and objects are created in schema of `others.username`.
+    fn schema_user(&self) -> anyhow::Result<Option<&str>> {
+        let dedicated = match self.connection.connections.others.get("dedicated_schema") {
+            Some(dedicated) => dedicated
+                .parse()
+                .map_err(|e| anyhow!("`connection.connections.others.dedicated_schema`: {e}"))?,
+            None => false,
+        };
+        if !dedicated {
+            return Ok(None);
+        }
+        let database = self.connection.database.as_str();
+        verify_identifier("schema", database)?;
+        Ok(Some(database))
+    }
+
+    /// `CREATE USER` and `GRANT` statements of dedicated schema.
+    fn create_user(&self, user: &str) -> anyhow::Result<[String; 2]> {
+        let password = match self.connection.connections.others.get("schema_password") {
+            Some(password) => password.clone(),
+            None => self.other("password")?,
+        };
+        if password.contains('"') {
+            return Err(anyhow!("Password of schema {user} should not contain `\"`"));
+        }
+        let grants = self
+            .connection
+            .connections
+            .others
+            .get("grants")
+            .map_or(DEFAULT_GRANTS, String::as_str);
+        Ok([
+            format!("CREATE USER {user} IDENTIFIED BY \"{password}\""),
+            format!("GRANT {grants} TO {user}"),
+        ])
+    }
+
+    /// Load client library of `others.library`, which is linked at build time without feature `dynamic`.
+    fn load_library(&self) -> anyhow::Result<()> {
+        let library = self
+            .connection
+            .connections
+            .others
+            .get("library")
+            .map_or(DEFAULT_LIBRARY, String::as_str);
+        native::load(library).map_err(|e| {
+            anyhow!("Could not load YashanDB client library {library:?}: {e}, install YashanDB client or set `connection.connections.others.library`")
+        })
+    }
+
+    /// Connect and switch to dedicated schema if any.
+    async fn connect(&self, role: Role) -> anyhow::Result<Connection> {
+        self.connect_to(role, self.schema_user()?).await
+    }
+
+    async fn connect_to(&self, role: Role, schema: Option<&str>) -> anyhow::Result<Connection> {
+        self.load_library()?;
+        let options = self.connection.connections.options(role);
+        verify_options(role, options)?;
+        let url = self.connection.connections.url(role).to_string();
+        let username = self.other("username")?;
+        let password = self.other("password")?;
+        let connecting = spawn_blocking(move || Connection::connect(&url, &username, &password));
+        let conn = match options.connect_timeout {
+            Some(limit) => timeout(limit, connecting)
+                .await
+                .map_err(|_| anyhow!("Connecting timed out after {limit:?}"))??,
+            None => connecting.await?,
+        }?;
+        if schema.is_none() && options.session.is_empty() {
+            return Ok(conn);
+        }
+
+        let conn = Arc::new(Mutex::new(conn));
+        let mut exec = SimpleExecutor::new(conn.clone())?;
+        if let Some(schema) = schema {
+            exec.execute(&format!("ALTER SESSION SET CURRENT_SCHEMA = {schema}"))
+                .await?;
+        }
+        for (name, value) in &options.session {
+            exec.execute(&alter_session(name, value)?).await?;
+        }
+        drop(exec);
+        Arc::into_inner(conn)
+            .and_then(|conn| conn.into_inner().ok())
+            .ok_or_else(|| anyhow!("Connection is still in use after session altered"))
+    }
+}
+
+/// Single `COUNT(*)` column.
+#[derive(Default, Clone, Copy)]
+struct Count {
+    rows: u32,
+}
+
+/// Query `COUNT(*)` of SQL, which blocks until fetched.
+fn count(conn: Arc<Mutex<Connection>>, sql: &str) -> anyhow::Result<u64> {
+    let mut prepared = Prepared::new(conn, sql, |b, p: &mut Count| b.column(1, &mut p.rows))?;
+    let count = prepared
+        .query(Count::default())?
+        .next()
+        .ok_or_else(|| anyhow!("No rows returned by {sql}"))??;
+    Ok(count.rows as u64)
+}
+
+/// TLS should be configured on server and by `yascli` itself, which is not exposed by its C API. Statement timeout
+/// is enforced by client for calls of terminals only.
+fn verify_options(role: Role, options: &ConnectionOptions) -> anyhow::Result<()> {
+    if options.tls.is_some() {
+        return Err(anyhow!(
+            "`connection.connections.options.{role}.tls` is not supported by YashanDB, configure SSL of server and client instead"
+        ));
+    }
+    if options.statement_timeout.is_some() && role != Role::Benchmark {
+        return Err(anyhow!(
+            "`connection.connections.options.{role}.statement_timeout` is supported by YashanDB for benchmark only"
+        ));
+    }
+    Ok(())
+}
+
+/// Names are formatted into SQL without quoting.
+fn verify_identifier(kind: &str, name: &str) -> anyhow::Result<()> {
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(anyhow!("Invalid {kind} name: {name:?}"));
+    }
+    Ok(())
+}
+
+/// Numbers and boolean are kept as is, others are quoted as strings.
+fn alter_session(name: &str, value: &str) -> anyhow::Result<String> {
+    verify_identifier("session parameter", name)?;
+    let value = if value.parse::<f64>().is_ok()
+        || ["TRUE", "FALSE"].contains(&value.to_uppercase().as_str())
+    {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "''"))
+    };
+    Ok(format!("ALTER SESSION SET {name} = {value}"))
 }
 
 #[async_trait::async_trait]
 impl Sut for YasdbSut {
-    async fn terminal(&self, _id: u32) -> anyhow::Result<Box<dyn Terminal>> {
-        Ok(Box::new(YasdbTerminal::new(
-            self.connect(self.connection.connections.benchmark.clone())
-                .await?,
-            self.warehouse_count,
-        )))
+    fn verify_config(&self) -> anyhow::Result<()> {
+        self.other("username")?;
+        self.other("password")?;
+        self.threads()?;
+        if let Some(user) = self.schema_user()? {
+            self.create_user(user)?;
+        }
+        if self.batch_size == 0 {
+            return Err(anyhow!("`loader.batch_size` must be greater than zero"));
+        }
+        for role in Role::ALL {
+            verify_options(role, self.connection.connections.options(role))?;
+        }
+        Ok(())
+    }
+
+    async fn ping(&self, role: Role) -> anyhow::Result<()> {
+        self.connect(role).await?;
+        Ok(())
+    }
+
+    async fn verify_load(&self) -> anyhow::Result<()> {
+        let conn = Arc::new(Mutex::new(self.connect(Role::Schema).await?));
+        for (table, expected) in expected_rows(self.warehouse_count, &self.scale) {
+            let conn = conn.clone();
+            let actual =
+                spawn_blocking(move || count(conn, &format!("SELECT COUNT(*) FROM {table}")))
+                    .await??;
+            info!(table, actual, ?expected, "Rows counted");
+            if !expected.contains(&actual) {
+                return Err(CheckError::Cardinality {
+                    table,
+                    actual,
+                    expected,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    async fn check_consistency(&self) -> anyhow::Result<()> {
+        let conn = Arc::new(Mutex::new(self.connect(Role::Schema).await?));
+        for (condition, sql) in CONSISTENCY_CONDITIONS {
+            let conn = conn.clone();
+            let violations = spawn_blocking(move || count(conn, sql)).await??;
+            info!(condition, violations, "Condition checked");
+            if violations != 0 {
+                return Err(CheckError::Consistency {
+                    condition,
+                    violations,
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    async fn terminal(&self, id: u32) -> anyhow::Result<Box<dyn Terminal>> {
+        let conn = self.connect(Role::Benchmark).await?;
+        let warehouse_count = self.warehouse_count;
+        let limit = self
+            .connection
+            .connections
+            .options(Role::Benchmark)
+            .statement_timeout;
+        let worker = self.worker(id)?;
+        let terminal_worker = worker.clone();
+        Ok(Box::new(
+            worker
+                .run(move || YasdbTerminal::new(conn, warehouse_count, terminal_worker, limit))
+                .await??,
+        ))
     }
 
     async fn build_schema(&self) -> anyhow::Result<()> {
-        let conn = self
-            .connect(self.connection.connections.schema.to_string())
-            .await?;
-        // let db_ddl = format!("create database {}", self.connection.database);
-        let sql_set = [
-            // `drop database` is not supported, so database should be created manually.
-            // db_ddl.as_str(),
-            r"CREATE TABLE CUSTOMER (C_ID NUMBER(5, 0), C_D_ID NUMBER(2, 0), C_W_ID NUMBER(6, 0), C_FIRST VARCHAR2(16), C_MIDDLE CHAR(2), C_LAST VARCHAR2(16), C_STREET_1 VARCHAR2(20), C_STREET_2 VARCHAR2(20), C_CITY VARCHAR2(20), C_STATE CHAR(2), C_ZIP CHAR(9), C_PHONE CHAR(16), C_SINCE DATE, C_CREDIT CHAR(2), C_CREDIT_LIM NUMBER(12, 2), C_DISCOUNT NUMBER(4, 4), C_BALANCE NUMBER(12, 2), C_YTD_PAYMENT NUMBER(12, 2), C_PAYMENT_CNT NUMBER(8, 0), C_DELIVERY_CNT NUMBER(8, 0), C_DATA VARCHAR2(500))",
-            r"CREATE TABLE DISTRICT (D_ID NUMBER(2, 0), D_W_ID NUMBER(6, 0), D_YTD NUMBER(12, 2), D_TAX NUMBER(4, 4), D_NEXT_O_ID NUMBER, D_NAME VARCHAR2(10), D_STREET_1 VARCHAR2(20), D_STREET_2 VARCHAR2(20), D_CITY VARCHAR2(20), D_STATE CHAR(2), D_ZIP CHAR(9))",
-            r"CREATE TABLE HISTORY (H_C_ID NUMBER, H_C_D_ID NUMBER, H_C_W_ID NUMBER, H_D_ID NUMBER, H_W_ID NUMBER, H_DATE DATE, H_AMOUNT NUMBER(6, 2), H_DATA VARCHAR2(24)) ",
-            r"CREATE TABLE ITEM (I_ID NUMBER(6, 0), I_IM_ID NUMBER, I_NAME VARCHAR2(24), I_PRICE NUMBER(5, 2), I_DATA VARCHAR2(50))",
-            r"CREATE TABLE WAREHOUSE (W_ID NUMBER(6, 0), W_YTD NUMBER(12, 2), W_TAX NUMBER(4, 4), W_NAME VARCHAR2(10), W_STREET_1 VARCHAR2(20), W_STREET_2 VARCHAR2(20), W_CITY VARCHAR2(20), W_STATE CHAR(2), W_ZIP CHAR(9))",
-            r"CREATE TABLE STOCK (S_I_ID NUMBER(6, 0), S_W_ID NUMBER(6, 0), S_QUANTITY NUMBER(6, 0), S_DIST_01 CHAR(24), S_DIST_02 CHAR(24), S_DIST_03 CHAR(24), S_DIST_04 CHAR(24), S_DIST_05 CHAR(24), S_DIST_06 CHAR(24), S_DIST_07 CHAR(24), S_DIST_08 CHAR(24), S_DIST_09 CHAR(24), S_DIST_10 CHAR(24), S_YTD NUMBER(10, 0), S_ORDER_CNT NUMBER(6, 0), S_REMOTE_CNT NUMBER(6, 0), S_DATA VARCHAR2(50))",
-            r"CREATE TABLE NEW_ORDER (NO_W_ID NUMBER, NO_D_ID NUMBER, NO_O_ID NUMBER, CONSTRAINT INORD PRIMARY KEY (NO_W_ID, NO_D_ID, NO_O_ID) ENABLE)",
-            r"CREATE TABLE OORDER (O_ID NUMBER, O_W_ID NUMBER, O_D_ID NUMBER, O_C_ID NUMBER, O_CARRIER_ID NUMBER, O_OL_CNT NUMBER, O_ALL_LOCAL NUMBER, O_ENTRY_D DATE)",
-            r"CREATE TABLE ORDER_LINE (OL_W_ID NUMBER, OL_D_ID NUMBER, OL_O_ID NUMBER, OL_NUMBER NUMBER, OL_I_ID NUMBER, OL_DELIVERY_D DATE, OL_AMOUNT NUMBER, OL_SUPPLY_W_ID NUMBER, OL_QUANTITY NUMBER, OL_DIST_INFO CHAR(24), CONSTRAINT IORDL PRIMARY KEY (OL_W_ID, OL_D_ID, OL_O_ID, OL_NUMBER) ENABLE)",
-        ];
+        self.schema.verify()?;
+        if let Some(user) = self.schema_user()? {
+            let conn = Arc::new(Mutex::new(self.connect_to(Role::Schema, None).await?));
+            let sql = format!(
+                "SELECT COUNT(*) FROM ALL_USERS WHERE USERNAME = '{}'",
+                user.to_uppercase()
+            );
+            let exists = {
+                let conn = conn.clone();
+                spawn_blocking(move || count(conn, &sql)).await?? > 0
+            };
+            if exists {
+                info!(user, "Schema user exists, tables are created in it");
+            } else {
+                let mut exec = SimpleExecutor::new(conn)?;
+                info!(user, "Creating schema");
+                for sql in self.create_user(user)? {
+                    exec.execute(&sql).await?;
+                }
+            }
+        }
+        let conn = self.connect(Role::Schema).await?;
         let mut exec = SimpleExecutor::new(Arc::new(Mutex::new(conn)))?;
-        for sql in sql_set {
-            info!(ddl = sql, "Creating table");
-            exec.execute(sql).await?;
+        for (table, warehouse_column) in TABLES {
+            let ddl = self.sql.get(&format!("tables/{table}.sql"))?;
+            let ddl = format!(
+                "{} {}",
+                ddl.trim_end().trim_end_matches(';'),
+                self.schema
+                    .yasdb_suffix(warehouse_column, self.warehouse_count)
+            );
+            info!(ddl, "Creating table");
+            exec.execute(&ddl).await?;
         }
         Ok(())
     }
 
     async fn after_loaded(&self) -> anyhow::Result<()> {
-        let conn = self
-            .connect(self.connection.connections.schema.to_string())
-            .await?;
+        let conn = self.connect(Role::Schema).await?;
         let mut exec = SimpleExecutor::new(Arc::new(Mutex::new(conn)))?;
 
-        // Build indexes
-        let sql_set = [
-            "CREATE UNIQUE INDEX CUSTOMER_I1 ON CUSTOMER (C_W_ID, C_D_ID, C_ID)",
-            "CREATE UNIQUE INDEX CUSTOMER_I2 ON CUSTOMER (C_LAST, C_D_ID, C_W_ID, C_FIRST)",
-            "CREATE UNIQUE INDEX DISTRICT_I1 ON DISTRICT (D_W_ID, D_ID)",
-            "CREATE UNIQUE INDEX ITEM_I1 ON ITEM (I_ID)",
-            "CREATE UNIQUE INDEX OORDER_I1 ON OORDER (O_W_ID, O_D_ID, O_ID)",
-            "CREATE UNIQUE INDEX OORDER_I2 ON OORDER (O_W_ID, O_D_ID, O_C_ID, O_ID)",
-            "CREATE UNIQUE INDEX STOCK_I1 ON STOCK (S_I_ID, S_W_ID)",
-            "CREATE UNIQUE INDEX WAREHOUSE_I1 ON WAREHOUSE (W_ID)",
-        ];
         info!("Building indexes...");
-        for sql in sql_set {
-            exec.execute(sql).await?;
+        for sql in self.sql.statements("indexes.sql")? {
+            exec.execute(&sql).await?;
         }
         info!("Indexes created.");
 
         info!("Creating view stock_item...");
-        exec.execute("CREATE OR REPLACE VIEW STOCK_ITEM (I_ID, S_W_ID, I_PRICE, I_NAME, I_DATA, S_DATA, S_QUANTITY, S_ORDER_CNT, S_YTD, S_REMOTE_CNT, S_DIST_01, S_DIST_02, S_DIST_03, S_DIST_04, S_DIST_05, S_DIST_06, S_DIST_07, S_DIST_08, S_DIST_09, S_DIST_10) AS SELECT /*+ LEADING(S) USE_NL(I) */ I.I_ID, S_W_ID, I.I_PRICE, I.I_NAME, I.I_DATA, S_DATA, S_QUANTITY, S_ORDER_CNT, S_YTD, S_REMOTE_CNT, S_DIST_01, S_DIST_02, S_DIST_03, S_DIST_04, S_DIST_05, S_DIST_06, S_DIST_07, S_DIST_08, S_DIST_09, S_DIST_10 FROM STOCK S, ITEM I WHERE I.I_ID = S.S_I_ID").await?;
+        exec.execute(&self.sql.get("stock_item.sql")?).await?;
         info!("Views created.");
 
-        // Create procedures
-        let sql_set = [
-            include_str!("../sql/new_order.sql"),
-            include_str!("../sql/payment.sql"),
-            include_str!("../sql/delivery.sql"),
-            include_str!("../sql/order_status.sql"),
-            include_str!("../sql/stock_level.sql"),
-        ];
         info!("Creating procedures...");
-        for sql in sql_set {
-            exec.execute(sql).await?;
+        for name in PROCEDURES {
+            exec.execute(&self.sql.get(name)?).await?;
         }
         info!("Procedures created.");
 
@@ -141,49 +449,158 @@ impl Sut for YasdbSut {
     }
 
     async fn destroy_schema(&self) -> anyhow::Result<()> {
-        let conn = self
-            .connect(self.connection.connections.schema.to_string())
-            .await?;
-        let conn = Arc::new(Mutex::new(conn));
-
-        // Drop tables
-        let tables = [
-            "CUSTOMER",
-            "DISTRICT",
-            "HISTORY",
-            "ITEM",
-            "WAREHOUSE",
-            "STOCK",
-            "NEW_ORDER",
-            "OORDER",
-            "ORDER_LINE",
-        ];
-        let mut exec = SimpleExecutor::new(conn.clone())?;
-        for table in tables {
-            info!(table, "Dropping table");
-            match exec.execute(&format!("drop table {table}")).await {
-                Ok(_) => info!(table, "Table dropped"),
-                Err(e) => warn!(table, ?e, "Failed to drop table"),
-            }
+        if let Some(user) = self.schema_user()? {
+            let conn = self.connect_to(Role::Schema, None).await?;
+            info!(user, "Dropping schema");
+            SimpleExecutor::new(Arc::new(Mutex::new(conn)))?
+                .execute(&format!("DROP USER {user} CASCADE"))
+                .await?;
+            info!(user, "Schema dropped");
+            return Ok(());
         }
 
-        // Drop procedures
-        let procedures = ["NEWORD", "OSTAT", "PAYMENT", "DELIVERY", "SLEV"];
-        let mut exec = SimpleExecutor::new(conn)?;
-        for procedure in procedures {
-            info!(procedure, "Dropping procedure");
-            match exec.execute(&format!("drop procedure {procedure}")).await {
-                Ok(_) => info!(procedure, "Table dropped"),
-                Err(e) => warn!(procedure, ?e, "Failed to drop procedure"),
+        let conn = self.connect(Role::Schema).await?;
+        let mut exec = SimpleExecutor::new(Arc::new(Mutex::new(conn)))?;
+        for (kind, name) in OBJECTS {
+            match exec.execute(&format!("DROP {kind} {name}")).await {
+                Ok(_) => info!(kind, name, "Dropped"),
+                Err(e) => warn!(kind, name, ?e, "Failed to drop"),
             }
         }
         Ok(())
     }
 
+    /// Schema exists if table `WAREHOUSE` does, a dedicated user without tables is built again.
+    async fn schema_exists(&self) -> anyhow::Result<bool> {
+        let (conn, sql) = match self.schema_user()? {
+            Some(user) => (
+                self.connect_to(Role::Schema, None).await?,
+                format!(
+                    "SELECT COUNT(*) FROM ALL_TABLES WHERE OWNER = '{}' AND TABLE_NAME = 'WAREHOUSE'",
+                    user.to_uppercase()
+                ),
+            ),
+            None => (
+                self.connect(Role::Schema).await?,
+                "SELECT COUNT(*) FROM USER_TABLES WHERE TABLE_NAME = 'WAREHOUSE'".to_string(),
+            ),
+        };
+        let conn = Arc::new(Mutex::new(conn));
+        Ok(spawn_blocking(move || count(conn, &sql)).await?? > 0)
+    }
+
     async fn loader(&self) -> anyhow::Result<Box<dyn Loader>> {
         Ok(Box::new(YasdbLoader::new(
-            self.connect(self.connection.connections.loader.clone())
-                .await?,
+            self.connect(Role::Loader).await?,
+            self.scale,
+            self.load_method,
+            self.batch_size,
         )))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use rsqlbench_core::{cfg::Connection, tpcc::sut::Sut};
+
+    use crate::native::mock::{mock, Call};
+
+    use super::YasdbSut;
+
+    fn sut(database: &str, others: &[(&str, &str)]) -> YasdbSut {
+        let mut builder = config::Config::builder()
+            .set_override("database", database)
+            .unwrap()
+            .set_override("connections.schema", "127.0.0.1:1688")
+            .unwrap()
+            .set_override("connections.loader", "127.0.0.1:1688")
+            .unwrap()
+            .set_override("connections.benchmark", "127.0.0.1:1688")
+            .unwrap()
+            .set_override("connections.others.username", "sys")
+            .unwrap()
+            .set_override("connections.others.password", "yasdb_123")
+            .unwrap();
+        for (key, value) in others {
+            builder = builder
+                .set_override(format!("connections.others.{key}"), *value)
+                .unwrap();
+        }
+        let connection: Connection = builder.build().unwrap().try_deserialize().unwrap();
+        YasdbSut::new(
+            connection,
+            1,
+            Default::default(),
+            Default::default(),
+            1000,
+            Default::default(),
+        )
+    }
+
+    const DEDICATED: (&str, &str) = ("dedicated_schema", "true");
+
+    #[test]
+    fn schema_user() {
+        assert_eq!(sut("tpcc", &[]).schema_user().unwrap(), None);
+        assert_eq!(
+            sut("tpcc", &[DEDICATED]).schema_user().unwrap(),
+            Some("tpcc")
+        );
+        assert!(sut("", &[DEDICATED]).schema_user().is_err());
+        assert!(sut("tpcc; DROP USER sys", &[DEDICATED])
+            .schema_user()
+            .is_err());
+        assert!(sut("tpcc", &[("dedicated_schema", "yes")])
+            .schema_user()
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn build_existing_user() {
+        let mock = mock();
+        mock.rows(1, &[1u32]);
+        sut("tpcc", &[DEDICATED]).build_schema().await.unwrap();
+        let calls = mock.calls();
+        assert!(!calls
+            .iter()
+            .any(|c| matches!(c, Call::DirectExecute(sql) if sql.starts_with("CREATE USER"))));
+        assert!(calls.contains(&Call::DirectExecute(
+            "ALTER SESSION SET CURRENT_SCHEMA = tpcc".to_string()
+        )));
+    }
+
+    #[tokio::test]
+    async fn build_new_user() {
+        let mock = mock();
+        mock.rows(1, &[0u32]);
+        sut("tpcc", &[DEDICATED]).build_schema().await.unwrap();
+        assert!(mock
+            .calls()
+            .contains(&Call::DirectExecute("GRANT DBA TO tpcc".to_string())));
+    }
+
+    #[test]
+    fn create_user() {
+        assert_eq!(
+            sut("tpcc", &[]).create_user("tpcc").unwrap(),
+            [
+                r#"CREATE USER tpcc IDENTIFIED BY "yasdb_123""#,
+                "GRANT DBA TO tpcc",
+            ]
+        );
+        let sut = sut(
+            "tpcc",
+            &[
+                ("schema_password", "tpcc_123"),
+                ("grants", "CONNECT, RESOURCE"),
+            ],
+        );
+        assert_eq!(
+            sut.create_user("tpcc").unwrap(),
+            [
+                r#"CREATE USER tpcc IDENTIFIED BY "tpcc_123""#,
+                "GRANT CONNECT, RESOURCE TO tpcc",
+            ]
+        );
+    }
+}
@@ -1,53 +1,279 @@
 use std::{
-    ptr::null_mut,
     sync::{Arc, Mutex},
+    time::Duration,
 };
 
-use rsqlbench_core::tpcc::{
-    sut::Terminal,
-    transaction::{
-        CustomerSelector, Delivery, DeliveryOut, NewOrder, NewOrderLineOut, NewOrderOut,
-        NewOrderRollbackOut, OrderStatus, OrderStatusOut, Payment, PaymentOut, StockLevel,
-        StockLevelOut,
+use crate::{
+    wrapper::{Connection, Date, Error, ErrorKind, Prepared, SimpleExecutor, Text},
+    Worker,
+};
+use anyhow::anyhow;
+use rsqlbench_core::{
+    cfg::tpcc::IsolationLevel,
+    tpcc::{
+        sut::{generic_direct::Executor, statement_timeout, Aborted, Terminal},
+        transaction::{
+            CustomerSelector, Delivery, DeliveryOut, NewOrder, NewOrderLineOut, NewOrderOut,
+            NewOrderRollbackOut, OrderStatus, OrderStatusOut, Payment, PaymentOut, StockLevel,
+            StockLevelOut,
+        },
     },
 };
 use time::OffsetDateTime;
-use tokio::task::spawn_blocking;
-use tracing::trace;
 
-use crate::{
-    guard_yac_call,
-    native::{
-        yacBindParameter, yacExecute, yacPrepare, EnYacExtType_YAC_SQLT_DATE,
-        EnYacExtType_YAC_SQLT_FLOAT, EnYacExtType_YAC_SQLT_INTEGER, EnYacExtType_YAC_SQLT_VARCHAR2,
-        EnYacParamDirection_YAC_PARAM_OUTPUT, YacParamDirection, YacUint16, YacUint32,
-    },
-    wrapper::{Error, Statement, StatementHandle},
-    Connection,
-};
+#[derive(Default, Clone, Copy)]
+struct NewOrderParams {
+    warehouse_id: u32,
+    warehouse_count: u32,
+    district_id: u32,
+    customer_id: u32,
+    ol_count: u32,
+    rollback: u32,
+    discount: f32,
+    last_name: Text<16>,
+    credit: Text<3>,
+    district_tax: f32,
+    warehouse_tax: f32,
+    order_id: u32,
+}
+
+#[derive(Default, Clone, Copy)]
+struct PaymentParams {
+    warehouse_id: u32,
+    district_id: u32,
+    customer_warehouse_id: u32,
+    customer_district_id: u32,
+    customer_id: u32,
+    by_name: u32,
+    amount: f32,
+    customer_last_name: Text<16>,
+    wh_street1: Text<21>,
+    wh_street2: Text<21>,
+    wh_city: Text<21>,
+    wh_state: Text<3>,
+    wh_zip: Text<10>,
+    d_street1: Text<21>,
+    d_street2: Text<21>,
+    d_city: Text<21>,
+    d_state: Text<3>,
+    d_zip: Text<10>,
+    customer_first_name: Text<17>,
+    customer_middle_name: Text<3>,
+    customer_street1: Text<21>,
+    customer_street2: Text<21>,
+    customer_city: Text<21>,
+    customer_state: Text<3>,
+    customer_zip: Text<10>,
+    customer_phone: Text<17>,
+    since: Text<20>,
+    customer_credit: Text<3>,
+    customer_credit_limit: f32,
+    customer_discount: f32,
+    customer_balance: f32,
+    customer_data: Text<501>,
+}
+
+#[derive(Default, Clone, Copy)]
+struct OrderStatusParams {
+    warehouse_id: u32,
+    district_id: u32,
+    customer_id: u32,
+    by_name: u32,
+    last_name: Text<16>,
+    first_name: Text<17>,
+    middle_name: Text<3>,
+    balance: f32,
+    order_id: u32,
+    entry_date: Date,
+    carrier_id: u32,
+}
+
+#[derive(Default, Clone, Copy)]
+struct DeliveryParams {
+    warehouse_id: u32,
+    carrier_id: u32,
+}
+
+#[derive(Default, Clone, Copy)]
+struct StockLevelParams {
+    warehouse_id: u32,
+    district_id: u32,
+    threshold: u32,
+    stock_count: u32,
+}
+
+type Shared<P> = Arc<Mutex<Prepared<P>>>;
+
+fn shared<P>(prepared: Prepared<P>) -> Shared<P> {
+    Arc::new(Mutex::new(prepared))
+}
+
+/// Roll back and turn conflicts into [`Aborted`], the failed call may leave its transaction open.
+fn check_aborted(conn: &Mutex<Connection>, e: Error) -> anyhow::Error {
+    if !e.is_aborted() {
+        return e.into();
+    }
+    match conn.lock().unwrap().rollback() {
+        Ok(()) => Aborted(e.to_string()).into(),
+        Err(e) => e.into(),
+    }
+}
 
 pub struct YasdbTerminal {
     conn: Arc<Mutex<Connection>>,
+    worker: Worker,
     warehouse_count: u32,
+    statement_timeout: Option<Duration>,
+    new_order: Shared<NewOrderParams>,
+    payment: Shared<PaymentParams>,
+    order_status: Shared<OrderStatusParams>,
+    delivery: Shared<DeliveryParams>,
+    stock_level: Shared<StockLevelParams>,
 }
 
 impl YasdbTerminal {
-    pub fn new(conn: Connection, warehouse_count: u32) -> Self {
-        Self {
-            conn: Arc::new(Mutex::new(conn)),
+    /// Prepare all procedure calls, which blocks until all of them are prepared.
+    ///
+    /// Transactions are executed by `worker` later, which fail if not returned within `statement_timeout`. Timed out
+    /// calls are not cancelled but keep the connection until returned.
+    pub fn new(
+        conn: Connection,
+        warehouse_count: u32,
+        worker: Worker,
+        statement_timeout: Option<Duration>,
+    ) -> Result<Self, Error> {
+        let conn = Arc::new(Mutex::new(conn));
+        Ok(Self {
+            new_order: shared(Prepared::new(
+                conn.clone(),
+                "CALL NEWORD(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, now())",
+                |b, p: &mut NewOrderParams| {
+                    b.input(1, &mut p.warehouse_id)?;
+                    b.input(2, &mut p.warehouse_count)?;
+                    b.input(3, &mut p.district_id)?;
+                    b.input(4, &mut p.customer_id)?;
+                    b.input(5, &mut p.ol_count)?;
+                    b.input(6, &mut p.rollback)?;
+                    b.output(7, &mut p.discount)?;
+                    b.output(8, &mut p.last_name)?;
+                    b.output(9, &mut p.credit)?;
+                    b.output(10, &mut p.district_tax)?;
+                    b.output(11, &mut p.warehouse_tax)?;
+                    b.output(12, &mut p.order_id)
+                },
+            )?),
+            payment: shared(Prepared::new(
+                conn.clone(),
+                "CALL PAYMENT(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, now())",
+                |b, p: &mut PaymentParams| {
+                    b.input(1, &mut p.warehouse_id)?;
+                    b.input(2, &mut p.district_id)?;
+                    b.input(3, &mut p.customer_warehouse_id)?;
+                    b.input(4, &mut p.customer_district_id)?;
+                    b.output(5, &mut p.customer_id)?;
+                    b.input(6, &mut p.by_name)?;
+                    b.input(7, &mut p.amount)?;
+                    b.output(8, &mut p.customer_last_name)?;
+                    b.output(9, &mut p.wh_street1)?;
+                    b.output(10, &mut p.wh_street2)?;
+                    b.output(11, &mut p.wh_city)?;
+                    b.output(12, &mut p.wh_state)?;
+                    b.output(13, &mut p.wh_zip)?;
+                    b.output(14, &mut p.d_street1)?;
+                    b.output(15, &mut p.d_street2)?;
+                    b.output(16, &mut p.d_city)?;
+                    b.output(17, &mut p.d_state)?;
+                    b.output(18, &mut p.d_zip)?;
+                    b.output(19, &mut p.customer_first_name)?;
+                    b.output(20, &mut p.customer_middle_name)?;
+                    b.output(21, &mut p.customer_street1)?;
+                    b.output(22, &mut p.customer_street2)?;
+                    b.output(23, &mut p.customer_city)?;
+                    b.output(24, &mut p.customer_state)?;
+                    b.output(25, &mut p.customer_zip)?;
+                    b.output(26, &mut p.customer_phone)?;
+                    b.output(27, &mut p.since)?;
+                    b.output(28, &mut p.customer_credit)?;
+                    b.output(29, &mut p.customer_credit_limit)?;
+                    b.output(30, &mut p.customer_discount)?;
+                    b.output(31, &mut p.customer_balance)?;
+                    b.output(32, &mut p.customer_data)
+                },
+            )?),
+            order_status: shared(Prepared::new(
+                conn.clone(),
+                "CALL OSTAT(?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)",
+                |b, p: &mut OrderStatusParams| {
+                    b.input(1, &mut p.warehouse_id)?;
+                    b.input(2, &mut p.district_id)?;
+                    b.output(3, &mut p.customer_id)?;
+                    b.input(4, &mut p.by_name)?;
+                    b.output(5, &mut p.last_name)?;
+                    b.output(6, &mut p.first_name)?;
+                    b.output(7, &mut p.middle_name)?;
+                    b.output(8, &mut p.balance)?;
+                    b.output(9, &mut p.order_id)?;
+                    b.output(10, &mut p.entry_date)?;
+                    b.output(11, &mut p.carrier_id)
+                },
+            )?),
+            delivery: shared(Prepared::new(
+                conn.clone(),
+                "CALL DELIVERY(?, ?, now())",
+                |b, p: &mut DeliveryParams| {
+                    b.input(1, &mut p.warehouse_id)?;
+                    b.input(2, &mut p.carrier_id)
+                },
+            )?),
+            stock_level: shared(Prepared::new(
+                conn.clone(),
+                "CALL SLEV(?, ?, ?, ?)",
+                |b, p: &mut StockLevelParams| {
+                    b.input(1, &mut p.warehouse_id)?;
+                    b.input(2, &mut p.district_id)?;
+                    b.input(3, &mut p.threshold)?;
+                    b.output(4, &mut p.stock_count)
+                },
+            )?),
+            conn,
+            worker,
             warehouse_count,
-        }
+            statement_timeout,
+        })
+    }
+
+    /// Run blocking call by worker within statement timeout.
+    async fn run<T: Send + 'static>(
+        &self,
+        call: impl FnOnce() -> T + Send + 'static,
+    ) -> anyhow::Result<T> {
+        statement_timeout(self.statement_timeout, self.worker.run(call)).await
     }
 }
 
 #[async_trait::async_trait]
 impl Terminal for YasdbTerminal {
+    async fn set_isolation(&mut self, level: IsolationLevel) -> anyhow::Result<()> {
+        match level {
+            IsolationLevel::ReadCommitted | IsolationLevel::Serializable => {
+                SimpleExecutor::new(self.conn.clone())?
+                    .execute(&format!(
+                        "ALTER SESSION SET ISOLATION_LEVEL = {}",
+                        level.sql()
+                    ))
+                    .await
+            }
+            _ => Err(anyhow!(
+                "Isolation level {level} is not supported by YashanDB"
+            )),
+        }
+    }
+
     async fn new_order(
         &mut self,
         input: &NewOrder,
     ) -> anyhow::Result<Result<NewOrderOut, NewOrderRollbackOut>> {
-        let stmt = Arc::new(Mutex::new(Statement::new(self.conn.clone())?));
-        let warehouse_count = self.warehouse_count;
+        let prepared = self.new_order.clone();
         let NewOrder {
             warehouse_id,
             district_id,
@@ -55,91 +281,36 @@ impl Terminal for YasdbTerminal {
             customer_id,
             order_lines,
         } = input;
-        let warehouse_id = *warehouse_id;
-        let district_id = *district_id;
-        let rollback_last = *rollback_last;
-        let customer_id = *customer_id;
-        let ol_count = order_lines.len();
-        {
-            let stmt = stmt.clone();
-            let sql = format!("CALL NEWORD({warehouse_id}, {warehouse_count}, {district_id}, {customer_id}, {ol_count}, {rollback_last}, ?, ?, ?, ?, ?, ?, now())");
-            trace!(sql);
-            spawn_blocking(move || unsafe {
-                guard_yac_call!(yacPrepare(
-                    stmt.lock().unwrap().handle().0,
-                    sql.as_ptr() as _,
-                    sql.len() as _
-                ))
-            })
-            .await??;
-        }
+        let params = NewOrderParams {
+            warehouse_id: *warehouse_id,
+            warehouse_count: self.warehouse_count,
+            district_id: *district_id as _,
+            customer_id: *customer_id,
+            ol_count: order_lines.len() as _,
+            rollback: *rollback_last as _,
+            ..Default::default()
+        };
+        let (warehouse_id, district_id, customer_id, rollback_last) =
+            (*warehouse_id, *district_id, *customer_id, *rollback_last);
+        let conn = self.conn.clone();
 
-        spawn_blocking(move || {
-            // Safety: all binding variables must live until yacExecute finished
-            let mut discount = 0.0f32;
-            let mut lastname = [0u8; 16];
-            let mut credit = [0u8; 3];
-            let mut d_tax = 0.0f32;
-            let mut w_tax = 0.0f32;
-            let mut next_order_id = 0u32;
-            let stmt_locked = stmt.lock().unwrap();
-            let handle = stmt_locked.handle();
-            unsafe {
-                yac_bind_parameter(
-                    handle,
-                    1,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut discount,
-                )?;
-                yac_bind_parameter_buffer(
-                    handle,
-                    2,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut lastname,
-                )?;
-                yac_bind_parameter_buffer(
-                    handle,
-                    3,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut credit,
-                )?;
-                yac_bind_parameter(
-                    handle,
-                    4,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut d_tax,
-                )?;
-                yac_bind_parameter(
-                    handle,
-                    5,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut w_tax,
-                )?;
-                yac_bind_parameter(
-                    handle,
-                    6,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_INTEGER,
-                    &mut next_order_id,
-                )?;
-                guard_yac_call!(yacExecute(handle.0))?;
-            };
+        self.run(move || {
+            let mut prepared = prepared.lock().unwrap();
+            prepared
+                .execute(params)
+                .map_err(|e| check_aborted(&conn, e))?;
+            let p = prepared.params();
             if !rollback_last {
                 Ok(Ok(NewOrderOut {
                     warehouse_id,
                     district_id,
                     customer_id,
-                    discount,
-                    credit: String::from_utf8_lossy(&credit).to_string(),
-                    customer_last_name: String::from_utf8_lossy(&lastname).to_string(),
-                    warehouse_tax: w_tax,
-                    district_tax: d_tax,
-                    order_id: next_order_id,
+                    discount: p.discount,
+                    credit: p.credit.to_string(),
+                    customer_last_name: p.last_name.to_string(),
+                    warehouse_tax: p.warehouse_tax,
+                    district_tax: p.district_tax,
+                    order_id: p.order_id,
                     order_lines: vec![NewOrderLineOut {
                         item_id: 0,
                         warehouse_id,
@@ -157,9 +328,9 @@ impl Terminal for YasdbTerminal {
                     warehouse_id,
                     district_id,
                     customer_id,
-                    credit: String::from_utf8_lossy(&credit).to_string(),
-                    customer_last_name: String::from_utf8_lossy(&lastname).to_string(),
-                    order_id: next_order_id,
+                    credit: p.credit.to_string(),
+                    customer_last_name: p.last_name.to_string(),
+                    order_id: p.order_id,
                 }))
             }
         })
@@ -167,463 +338,122 @@ impl Terminal for YasdbTerminal {
     }
 
     async fn payment(&mut self, input: &Payment) -> anyhow::Result<PaymentOut> {
-        let stmt = Arc::new(Mutex::new(Statement::new(self.conn.clone())?));
+        let prepared = self.payment.clone();
         let Payment {
             warehouse_id,
             district_id,
+            customer_warehouse_id,
+            customer_district_id,
             customer,
             amount,
-            ..
         } = input;
-        let warehouse_id = *warehouse_id;
-        let district_id = *district_id;
-        let amount = *amount;
-        let c_w_id = input.customer_warehouse_id();
-        let stmt = stmt;
-        let (by_name, c_last_name, mut customer_id) = match customer {
-            CustomerSelector::LastName(n) => (1, n.clone(), 0),
-            CustomerSelector::ID(id) => (0, "".to_string(), *id),
+        let (warehouse_id, district_id, amount) = (*warehouse_id, *district_id, *amount);
+        let (c_w_id, c_d_id) = (*customer_warehouse_id, *customer_district_id);
+        let (by_name, c_last_name, customer_id) = match customer {
+            CustomerSelector::LastName(n) => (1, n.as_str(), 0),
+            CustomerSelector::ID(id) => (0, "", *id),
         };
-        {
-            let stmt = stmt.clone();
-            let sql = format!(
-                r"CALL PAYMENT({warehouse_id}, {district_id}, {c_w_id}, {district_id}, ?, {by_name}, {amount}, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, now())"
-            );
-            trace!(sql);
-            spawn_blocking(move || unsafe {
-                guard_yac_call!(yacPrepare(
-                    stmt.lock().unwrap().handle().0,
-                    sql.as_ptr() as _,
-                    sql.len() as _
-                ))
-            })
-            .await??;
-        }
+        let params = PaymentParams {
+            warehouse_id,
+            district_id: district_id as _,
+            customer_warehouse_id: c_w_id,
+            customer_district_id: c_d_id as _,
+            customer_id,
+            by_name,
+            amount,
+            customer_last_name: c_last_name.into(),
+            ..Default::default()
+        };
+        let conn = self.conn.clone();
 
-        spawn_blocking(move || {
-            // Safety: all binding variables must live until yacExecute finished
-            let mut customer_first_name = [0u8; 17];
-            let mut customer_last_name = [0u8; 16];
-            let mut customer_middle_name = [0u8; 3];
-            let mut customer_street1 = [0u8; 21];
-            let mut customer_street2 = [0u8; 21];
-            let mut customer_city = [0u8; 21];
-            let mut customer_state = [0u8; 3];
-            let mut customer_zip = [0u8; 10];
-            let mut customer_phone = [0u8; 17];
-            let mut customer_credit = [0u8; 3];
-            let mut customer_credit_limit = 0.0f32;
-            let mut customer_discount = 0.0f32;
-            let mut customer_balance = 0.0f32;
-            let mut customer_data = [0u8; 501];
-            let mut wh_street1 = [0u8; 21];
-            let mut wh_street2 = [0u8; 21];
-            let mut wh_city = [0u8; 21];
-            let mut wh_state = [0u8; 3];
-            let mut wh_zip = [0u8; 10];
-            let mut d_street1 = [0u8; 21];
-            let mut d_street2 = [0u8; 21];
-            let mut d_city = [0u8; 21];
-            let mut d_state = [0u8; 3];
-            let mut d_zip = [0u8; 10];
-            let mut since = [0u8; 20];
-            customer_last_name[0..c_last_name.len()].copy_from_slice(c_last_name.as_bytes());
-            let stmt_locked = stmt.lock().unwrap();
-            let stmt = stmt_locked.handle();
-            unsafe {
-                yac_bind_parameter(
-                    stmt,
-                    1,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_INTEGER,
-                    &mut customer_id,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    2,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_last_name,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    3,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut wh_street1,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    4,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut wh_street2,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    5,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut wh_city,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    6,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut wh_state,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    7,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut wh_zip,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    8,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut d_street1,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    9,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut d_street2,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    10,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut d_city,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    11,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut d_state,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    12,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut d_zip,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    13,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_first_name,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    14,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_middle_name,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    15,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_street1,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    16,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_street2,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    17,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_city,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    18,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_state,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    19,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_zip,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    20,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_phone,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    21,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut since,
-                )?;
-                yac_bind_parameter_buffer(
-                    stmt,
-                    22,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut customer_credit,
-                )?;
-                yac_bind_parameter(
-                    stmt,
-                    23,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut customer_credit_limit,
-                )?;
-                yac_bind_parameter(
-                    stmt,
-                    24,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut customer_discount,
-                )?;
-                yac_bind_parameter(
-                    stmt,
-                    25,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut customer_balance,
-                )?;
-                yac_bind_parameter(
-                    stmt,
-                    26,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut customer_data,
-                )?;
+        self.run(move || {
+            let mut prepared = prepared.lock().unwrap();
+            let found = match prepared.execute(params) {
+                Ok(()) => true,
+                Err(e) if e.kind() == ErrorKind::NoDataFound => false,
+                Err(e) => return Err(check_aborted(&conn, e)),
             };
-            let result = unsafe { guard_yac_call!(yacExecute(stmt.0)) };
-            match result {
-                Ok(_) => Ok(PaymentOut {
-                    warehouse_id,
-                    district_id,
-                    customer_id,
-                    customer_warehouse_id: c_w_id,
-                    customer_district_id: district_id,
-                    amount,
-                    date: OffsetDateTime::now_utc(),
-                    warehouse_street: (
-                        String::from_utf8_lossy(&wh_street1).to_string(),
-                        String::from_utf8_lossy(&wh_street2).to_string(),
-                    ),
-                    warehouse_city: String::from_utf8_lossy(&wh_city).to_string(),
-                    warehouse_state: String::from_utf8_lossy(&wh_state).to_string(),
-                    warehouse_zip: String::from_utf8_lossy(&wh_zip).to_string(),
-                    district_street: (
-                        String::from_utf8_lossy(&d_street1).to_string(),
-                        String::from_utf8_lossy(&d_street2).to_string(),
-                    ),
-                    district_city: String::from_utf8_lossy(&d_city).to_string(),
-                    district_state: String::from_utf8_lossy(&d_state).to_string(),
-                    district_zip: String::from_utf8_lossy(&d_zip).to_string(),
-                    customer_first_name: String::from_utf8_lossy(&customer_first_name)
-                        .to_string()
-                        .into(),
-                    customer_last_name: String::from_utf8_lossy(&customer_last_name)
-                        .to_string()
-                        .into(),
-                    customer_middle_name: String::from_utf8_lossy(&customer_middle_name)
-                        .to_string()
-                        .into(),
-                    customer_street: (
-                        String::from_utf8_lossy(&customer_street1)
-                            .to_string()
-                            .into(),
-                        String::from_utf8_lossy(&customer_street2)
-                            .to_string()
-                            .into(),
-                    ),
-                    customer_city: String::from_utf8_lossy(&customer_city).to_string().into(),
-                    customer_state: String::from_utf8_lossy(&customer_state).to_string().into(),
-                    customer_zip: String::from_utf8_lossy(&customer_zip).to_string().into(),
-                    customer_phone: String::from_utf8_lossy(&customer_phone).to_string().into(),
-                    customer_since: OffsetDateTime::now_utc(),
-                    customer_credit: String::from_utf8_lossy(&customer_credit).to_string().into(),
-                    customer_credit_lim: Some(customer_credit_limit),
-                    customer_discount: Some(customer_discount),
-                    customer_balance: Some(customer_balance),
-                    customer_data: String::from_utf8_lossy(&customer_data).to_string().into(),
-                }),
-                Err(Error::YasClient(err)) if err.code == 5206 => Ok(PaymentOut {
-                    warehouse_id,
-                    district_id,
-                    customer_id,
-                    customer_warehouse_id: c_w_id,
-                    customer_district_id: district_id,
-                    amount,
-                    date: OffsetDateTime::now_utc(),
-                    warehouse_street: (
-                        String::from_utf8_lossy(&wh_street1).to_string(),
-                        String::from_utf8_lossy(&wh_street2).to_string(),
-                    ),
-                    warehouse_city: String::from_utf8_lossy(&wh_city).to_string(),
-                    warehouse_state: String::from_utf8_lossy(&wh_state).to_string(),
-                    warehouse_zip: String::from_utf8_lossy(&wh_zip).to_string(),
-                    district_street: (
-                        String::from_utf8_lossy(&d_street1).to_string(),
-                        String::from_utf8_lossy(&d_street2).to_string(),
-                    ),
-                    district_city: String::from_utf8_lossy(&d_city).to_string(),
-                    district_state: String::from_utf8_lossy(&d_state).to_string(),
-                    district_zip: String::from_utf8_lossy(&d_zip).to_string(),
-                    customer_first_name: None,
-                    customer_last_name: None,
-                    customer_middle_name: None,
-                    customer_street: (None, None),
-                    customer_city: None,
-                    customer_state: None,
-                    customer_zip: None,
-                    customer_phone: None,
-                    customer_since: OffsetDateTime::now_utc(),
-                    customer_credit: None,
-                    customer_credit_lim: None,
-                    customer_discount: None,
-                    customer_balance: None,
-                    customer_data: None,
-                }),
-                Err(e) => Err(e.into()),
-            }
+            let p = prepared.params();
+            let customer = |text: String| found.then_some(text);
+            Ok(PaymentOut {
+                warehouse_id,
+                district_id,
+                customer_id: p.customer_id,
+                customer_warehouse_id: c_w_id,
+                customer_district_id: c_d_id,
+                amount,
+                date: OffsetDateTime::now_utc(),
+                warehouse_street: (p.wh_street1.to_string(), p.wh_street2.to_string()),
+                warehouse_city: p.wh_city.to_string(),
+                warehouse_state: p.wh_state.to_string(),
+                warehouse_zip: p.wh_zip.to_string(),
+                district_street: (p.d_street1.to_string(), p.d_street2.to_string()),
+                district_city: p.d_city.to_string(),
+                district_state: p.d_state.to_string(),
+                district_zip: p.d_zip.to_string(),
+                customer_first_name: customer(p.customer_first_name.to_string()),
+                customer_last_name: customer(p.customer_last_name.to_string()),
+                customer_middle_name: customer(p.customer_middle_name.to_string()),
+                customer_street: (
+                    customer(p.customer_street1.to_string()),
+                    customer(p.customer_street2.to_string()),
+                ),
+                customer_city: customer(p.customer_city.to_string()),
+                customer_state: customer(p.customer_state.to_string()),
+                customer_zip: customer(p.customer_zip.to_string()),
+                customer_phone: customer(p.customer_phone.to_string()),
+                customer_since: found.then(OffsetDateTime::now_utc),
+                customer_credit: customer(p.customer_credit.to_string()),
+                customer_credit_lim: found.then_some(p.customer_credit_limit),
+                customer_discount: found.then_some(p.customer_discount),
+                customer_balance: found.then_some(p.customer_balance),
+                customer_data: customer(p.customer_data.to_string()),
+            })
         })
         .await?
     }
 
     async fn order_status(&mut self, input: &OrderStatus) -> anyhow::Result<OrderStatusOut> {
-        let stmt = Arc::new(Mutex::new(Statement::new(self.conn.clone())?));
+        let prepared = self.order_status.clone();
         let OrderStatus {
             warehouse_id,
             district_id,
             customer,
         } = input;
-        let warehouse_id = *warehouse_id;
-        let district_id = *district_id;
-        let (by_name, c_last_name, mut customer_id) = match customer {
-            CustomerSelector::LastName(n) => (1, n.clone(), 0),
-            CustomerSelector::ID(id) => (0, "".to_string(), *id),
+        let (warehouse_id, district_id) = (*warehouse_id, *district_id);
+        let (by_name, c_last_name, customer_id) = match customer {
+            CustomerSelector::LastName(n) => (1, n.as_str(), 0),
+            CustomerSelector::ID(id) => (0, "", *id),
         };
-        {
-            let stmt = stmt.clone();
-            let sql = format!(
-                "CALL OSTAT({warehouse_id}, {district_id}, ?, {by_name}, ?, ?, ?, ?, ?, ?, ?)"
-            );
-            trace!(sql);
-            spawn_blocking(move || unsafe {
-                guard_yac_call!(yacPrepare(
-                    stmt.lock().unwrap().handle().0,
-                    sql.as_ptr() as _,
-                    sql.len() as _
-                ))
-            })
-            .await??;
-        }
+        let params = OrderStatusParams {
+            warehouse_id,
+            district_id: district_id as _,
+            customer_id,
+            by_name,
+            last_name: c_last_name.into(),
+            ..Default::default()
+        };
+        let conn = self.conn.clone();
 
-        spawn_blocking(move || {
-            // Safety: all binding variables must live until yacExecute finished
-            let mut first_name = [0u8; 17];
-            let mut last_name = [0u8; 16];
-            let mut middle_name = [0u8; 3];
-            let mut balance = 0.0f32;
-            let mut order_id = 0u32;
-            let mut entdate = [0u8; 20];
-            let mut carrier_id = 0u8;
-            last_name[0..c_last_name.len()].copy_from_slice(c_last_name.as_bytes());
-            let stmt_locked = stmt.lock().unwrap();
-            let handle = stmt_locked.handle();
-            let executed = unsafe {
-                yac_bind_parameter(
-                    handle,
-                    1,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_INTEGER,
-                    &mut customer_id,
-                )?;
-                yac_bind_parameter_buffer(
-                    handle,
-                    2,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut last_name,
-                )?;
-                yac_bind_parameter_buffer(
-                    handle,
-                    3,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut first_name,
-                )?;
-                yac_bind_parameter_buffer(
-                    handle,
-                    4,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_VARCHAR2,
-                    &mut middle_name,
-                )?;
-                yac_bind_parameter(
-                    handle,
-                    5,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut balance,
-                )?;
-                yac_bind_parameter(
-                    handle,
-                    6,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_FLOAT,
-                    &mut order_id,
-                )?;
-                yac_bind_parameter_buffer(
-                    handle,
-                    7,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_DATE,
-                    &mut entdate,
-                )?;
-                yac_bind_parameter(
-                    handle,
-                    8,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_INTEGER,
-                    &mut carrier_id,
-                )?;
-                guard_yac_call!(yacExecute(handle.0))
-            };
+        self.run(move || {
+            let mut prepared = prepared.lock().unwrap();
+            let executed = prepared.execute(params);
+            let p = prepared.params();
             match executed {
-                Ok(_) => Ok(OrderStatusOut {
+                Ok(()) => Ok(OrderStatusOut {
                     warehouse_id,
                     district_id,
-                    customer_id: Some(customer_id),
-                    customer_last_name: Some(String::from_utf8_lossy(&last_name).to_string()),
-                    customer_middle_name: Some(String::from_utf8_lossy(&middle_name).to_string()),
-                    customer_first_name: Some(String::from_utf8_lossy(&first_name).to_string()),
-                    customer_balance: Some(balance),
-                    order_id: Some(order_id),
-                    carrier_id: Some(carrier_id),
+                    customer_id: Some(p.customer_id),
+                    customer_last_name: Some(p.last_name.to_string()),
+                    customer_middle_name: Some(p.middle_name.to_string()),
+                    customer_first_name: Some(p.first_name.to_string()),
+                    customer_balance: Some(p.balance),
+                    order_id: Some(p.order_id),
+                    carrier_id: Some(p.carrier_id as _),
                     entry_date: Some(OffsetDateTime::now_utc()),
                     order_lines: vec![],
                 }),
-                Err(Error::YasClient(e)) if e.code == 5206 => Ok(OrderStatusOut {
+                Err(e) if e.kind() == ErrorKind::NoDataFound => Ok(OrderStatusOut {
                     warehouse_id,
                     district_id,
                     customer_id: None,
@@ -636,130 +466,218 @@ impl Terminal for YasdbTerminal {
                     entry_date: None,
                     order_lines: vec![],
                 }),
-                Err(e) => Err(e),
+                Err(e) => Err(check_aborted(&conn, e)),
             }
         })
         .await?
-        .map_err(|x| x.into())
     }
 
     async fn delivery(&mut self, input: &Delivery) -> anyhow::Result<DeliveryOut> {
-        let stmt = Arc::new(Mutex::new(Statement::new(self.conn.clone())?));
+        let prepared = self.delivery.clone();
         let Delivery {
             warehouse_id,
             carrier_id,
         } = input;
-        let warehouse_id = *warehouse_id;
-        let carrier_id = *carrier_id;
-        let stmt = stmt.clone();
-        let sql = format!("CALL DELIVERY({warehouse_id}, {carrier_id}, now())");
-        trace!(sql);
-        let executed = spawn_blocking(move || -> Result<(), Error> {
-            unsafe {
-                let stmt_locked = stmt.lock().unwrap();
-                let handle = stmt_locked.handle();
-                guard_yac_call!(yacPrepare(handle.0, sql.as_ptr() as _, sql.len() as _))?;
-                guard_yac_call!(yacExecute(handle.0))
-            }
-        })
-        .await?;
-        match executed {
-            Ok(_) => Ok(DeliveryOut {
+        let (warehouse_id, carrier_id) = (*warehouse_id, *carrier_id);
+        let params = DeliveryParams {
+            warehouse_id,
+            carrier_id: carrier_id as _,
+        };
+        let conn = self.conn.clone();
+
+        self.run(move || match prepared.lock().unwrap().execute(params) {
+            Ok(()) => Ok(DeliveryOut {
                 warehouse_id,
                 carrier_id,
             }),
-            Err(Error::YasClient(e)) if e.code == 5206 => Ok(DeliveryOut {
+            Err(e) if e.kind() == ErrorKind::NoDataFound => Ok(DeliveryOut {
                 warehouse_id,
                 carrier_id,
             }),
-            Err(e) => Err(e.into()),
-        }
+            Err(e) => Err(check_aborted(&conn, e)),
+        })
+        .await?
     }
 
     async fn stock_level(&mut self, input: &StockLevel) -> anyhow::Result<StockLevelOut> {
-        let stmt = Arc::new(Mutex::new(Statement::new(self.conn.clone())?));
+        let prepared = self.stock_level.clone();
         let StockLevel {
             warehouse_id,
             district_id,
             threshold,
         } = input;
-        let warehouse_id = *warehouse_id;
-        let district_id = *district_id;
-        let threshold = *threshold;
-        {
-            let stmt = stmt.clone();
-            let sql = format!("CALL SLEV({warehouse_id}, {district_id}, {threshold}, ?)");
-            trace!(sql);
-            spawn_blocking(move || unsafe {
-                guard_yac_call!(yacPrepare(
-                    stmt.lock().unwrap().handle().0,
-                    sql.as_ptr() as _,
-                    sql.len() as _
-                ))
-            })
-            .await??;
-        }
+        let (warehouse_id, district_id, threshold) = (*warehouse_id, *district_id, *threshold);
+        let params = StockLevelParams {
+            warehouse_id,
+            district_id: district_id as _,
+            threshold: threshold as _,
+            ..Default::default()
+        };
+        let conn = self.conn.clone();
 
-        spawn_blocking(move || {
-            // Safety: all binding variables must live until yacExecute finished
-            let mut stock_count = 0u32;
-            let stmt_locked = stmt.lock().unwrap();
-            let handle = stmt_locked.handle();
-            unsafe {
-                yac_bind_parameter(
-                    handle,
-                    1,
-                    EnYacParamDirection_YAC_PARAM_OUTPUT,
-                    EnYacExtType_YAC_SQLT_INTEGER,
-                    &mut stock_count,
-                )?;
-                guard_yac_call!(yacExecute(handle.0))?;
-            };
+        self.run(move || {
+            let mut prepared = prepared.lock().unwrap();
+            prepared
+                .execute(params)
+                .map_err(|e| check_aborted(&conn, e))?;
             Ok(StockLevelOut {
                 warehouse_id,
                 district_id,
                 threshold,
-                low_stock: stock_count,
+                low_stock: prepared.params().stock_count,
             })
         })
         .await?
     }
 }
 
-unsafe fn yac_bind_parameter<T: Sized>(
-    stmt: &StatementHandle,
-    id: YacUint16,
-    direction: YacParamDirection,
-    ext_type: YacUint32,
-    value: &mut T,
-) -> Result<(), Error> {
-    guard_yac_call!(yacBindParameter(
-        stmt.0,
-        id,
-        direction,
-        ext_type,
-        value as *mut T as _,
-        std::mem::size_of::<T>() as _,
-        std::mem::size_of::<T>() as _,
-        null_mut()
-    ))
-}
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use rsqlbench_core::tpcc::{
+        model::ScaleModel,
+        sut::Terminal,
+        transaction::{CustomerSelector, NewOrder, Payment},
+    };
+
+    use rsqlbench_core::tpcc::sut::Aborted;
+
+    use crate::{
+        native::mock::{mock, Call, Diag},
+        wrapper::{Connection, Text, DEADLOCK, NO_DATA_FOUND},
+        Worker,
+    };
+
+    use super::YasdbTerminal;
+
+    fn terminal() -> YasdbTerminal {
+        let conn = Connection::connect("127.0.0.1:1688", "sys", "yasdb").unwrap();
+        YasdbTerminal::new(conn, 10, Worker::Blocking, None).unwrap()
+    }
+
+    fn payment() -> Payment {
+        let mut payment = Payment::generate(1, 1, 3, &ScaleModel::default());
+        payment.customer = CustomerSelector::LastName("BARBARBAR".to_string());
+        payment
+    }
+
+    #[tokio::test]
+    async fn new_order() {
+        let mock = mock();
+        let mut terminal = terminal();
+        mock.output(7, 0.25f32);
+        mock.output(8, Text::<16>::from("BARBARBAR"));
+        mock.output(9, Text::<3>::from("GC"));
+        mock.output(10, 0.1f32);
+        mock.output(11, 0.2f32);
+        mock.output(12, 3001u32);
+        let input = NewOrder {
+            warehouse_id: 1,
+            district_id: 3,
+            rollback_last: false,
+            customer_id: 42,
+            order_lines: vec![],
+        };
+        let Ok(out) = terminal.new_order(&input).await.unwrap() else {
+            panic!("Expected committed order");
+        };
+        assert_eq!(out.order_id, 3001);
+        assert_eq!(out.customer_last_name, "BARBARBAR");
+        assert_eq!(out.credit, "GC");
+        assert_eq!(out.discount, 0.25);
+        assert_eq!((out.district_tax, out.warehouse_tax), (0.1, 0.2));
+        assert_eq!(unsafe { mock.input::<u32>(2, 0) }, Some(10));
+        assert_eq!(unsafe { mock.input::<u32>(3, 0) }, Some(3));
+        assert_eq!(unsafe { mock.input::<u32>(4, 0) }, Some(42));
+    }
 
-unsafe fn yac_bind_parameter_buffer(
-    stmt: &StatementHandle,
-    id: YacUint16,
-    direction: YacParamDirection,
-    ext_type: YacUint32,
-    value: &mut [u8],
-) -> Result<(), Error> {
-    guard_yac_call!(yacBindParameter(
-        stmt.0,
-        id,
-        direction,
-        ext_type,
-        value.as_ptr() as *mut u8 as _,
-        value.len() as _,
-        value.len() as _,
-        null_mut()
-    ))
+    #[tokio::test]
+    async fn payment_by_name() {
+        let mock = mock();
+        let mut terminal = terminal();
+        mock.output(5, 42u32);
+        mock.output(11, Text::<21>::from("Hangzhou"));
+        mock.output(19, Text::<17>::from("Alice"));
+        mock.output(31, -10.0f32);
+        let mut input = payment();
+        (input.customer_warehouse_id, input.customer_district_id) = (2, 7);
+        let out = terminal.payment(&input).await.unwrap();
+        assert_eq!(out.customer_id, 42);
+        assert_eq!(
+            (out.customer_warehouse_id, out.customer_district_id),
+            (2, 7)
+        );
+        assert_eq!(out.warehouse_city, "Hangzhou");
+        assert_eq!(out.customer_first_name.as_deref(), Some("Alice"));
+        assert_eq!(out.customer_last_name.as_deref(), Some("BARBARBAR"));
+        assert_eq!(out.customer_balance, Some(-10.0));
+        assert_eq!(unsafe { mock.input::<u32>(1, 0) }, Some(1));
+        assert_eq!(unsafe { mock.input::<u32>(3, 0) }, Some(2));
+        assert_eq!(unsafe { mock.input::<u32>(4, 0) }, Some(7));
+        assert_eq!(unsafe { mock.input::<u32>(6, 0) }, Some(1));
+        assert_eq!(
+            unsafe { mock.input::<Text<16>>(8, 0) }.unwrap().to_string(),
+            "BARBARBAR"
+        );
+    }
+
+    #[tokio::test]
+    async fn prepared_once() {
+        let mock = mock();
+        let mut terminal = terminal();
+        let count = |f: fn(&Call) -> bool| mock.calls().iter().filter(|c| f(c)).count();
+        let prepared = count(|c| matches!(c, Call::Prepare(_) | Call::AllocHandle(_)));
+        for _ in 0..3 {
+            terminal.payment(&payment()).await.unwrap();
+        }
+        assert_eq!(
+            count(|c| matches!(c, Call::Prepare(_) | Call::AllocHandle(_))),
+            prepared
+        );
+        assert_eq!(count(|c| *c == Call::Execute), 3);
+    }
+
+    #[tokio::test]
+    async fn payment_customer_not_found() {
+        let mock = mock();
+        let mut terminal = terminal();
+        mock.fail("yacExecute", Diag::new(NO_DATA_FOUND, "no data found"));
+        let out = terminal.payment(&payment()).await.unwrap();
+        assert_eq!(out.customer_first_name, None);
+        assert_eq!(out.customer_balance, None);
+    }
+
+    #[tokio::test]
+    async fn payment_aborted() {
+        let mock = mock();
+        let mut terminal = terminal();
+        mock.fail("yacExecute", Diag::new(DEADLOCK, "deadlock detected"));
+        let e = terminal.payment(&payment()).await.unwrap_err();
+        assert!(e.is::<Aborted>());
+        assert_eq!(mock.calls().last(), Some(&Call::Rollback));
+    }
+
+    #[tokio::test]
+    async fn statement_timeout() {
+        let _mock = mock();
+        let mut terminal = terminal();
+        terminal.statement_timeout = Some(Duration::from_millis(10));
+        assert_eq!(terminal.run(|| 1).await.unwrap(), 1);
+        let e = terminal
+            .run(|| std::thread::sleep(Duration::from_millis(200)))
+            .await
+            .unwrap_err();
+        assert_eq!(e.to_string(), "Statement timed out after 10ms");
+    }
+
+    #[tokio::test]
+    async fn payment_failed() {
+        let mock = mock();
+        let mut terminal = terminal();
+        mock.fail("yacExecute", Diag::new(1, "unknown"));
+        let e = terminal.payment(&payment()).await.unwrap_err();
+        assert!(!e.is::<Aborted>());
+        assert_eq!(mock.calls().last(), Some(&Call::Execute));
+    }
 }
@@ -0,0 +1,173 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    str::FromStr,
+    thread,
+};
+
+use anyhow::anyhow;
+use tokio::{sync::oneshot, task::spawn_blocking};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+/// Threads running blocking calls of terminals, configured by `connection.connections.others.threads`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Threads {
+    /// Blocking pool of tokio, which is shared with other blocking tasks and sized by `max_blocking_threads`.
+    #[default]
+    Blocking,
+
+    /// Every terminal owns an OS thread.
+    Dedicated,
+
+    /// Pool of fixed count of OS threads shared by all terminals.
+    Pool(usize),
+}
+
+impl FromStr for Threads {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "blocking" => Ok(Self::Blocking),
+            "dedicated" => Ok(Self::Dedicated),
+            _ => match s.parse() {
+                Ok(size) if size > 0 => Ok(Self::Pool(size)),
+                _ => Err(format!(
+                    "Expected `blocking`, `dedicated` or size of pool, found {s:?}"
+                )),
+            },
+        }
+    }
+}
+
+/// Runs blocking calls and sends results back.
+#[derive(Clone)]
+pub enum Worker {
+    /// Calls are spawned onto blocking pool of tokio.
+    Blocking,
+
+    /// Calls are sent to own threads through channel.
+    Threads(async_channel::Sender<Job>),
+}
+
+impl Worker {
+    /// Spawn `count` threads receiving calls, which exit after all clones of worker dropped.
+    pub fn threads(name: &str, count: usize) -> std::io::Result<Self> {
+        let (sender, receiver) = async_channel::unbounded::<Job>();
+        for i in 0..count {
+            let receiver = receiver.clone();
+            thread::Builder::new()
+                .name(format!("{name}-{i}"))
+                .spawn(move || {
+                    while let Ok(job) = receiver.recv_blocking() {
+                        // Panics are reported to caller by dropped response sender.
+                        let _ = catch_unwind(AssertUnwindSafe(job));
+                    }
+                })?;
+        }
+        Ok(Self::Threads(sender))
+    }
+
+    /// Run blocking call and wait for its result.
+    pub async fn run<T: Send + 'static>(
+        &self,
+        call: impl FnOnce() -> T + Send + 'static,
+    ) -> anyhow::Result<T> {
+        match self {
+            Worker::Blocking => Ok(spawn_blocking(call).await?),
+            Worker::Threads(sender) => {
+                let (tx, rx) = oneshot::channel();
+                sender
+                    .send(Box::new(move || {
+                        let _ = tx.send(call());
+                    }))
+                    .await
+                    .map_err(|_| anyhow!("Threads of worker exited"))?;
+                rx.await.map_err(|_| anyhow!("Blocking call panicked"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{
+        thread,
+        time::{Duration, Instant},
+    };
+
+    use tokio::runtime::Builder;
+
+    use super::{Threads, Worker};
+
+    /// Terminals sleeping instead of calling database, which shows scheduling of blocking calls only.
+    const TERMINALS: usize = 256;
+    const CALLS: usize = 50;
+    const LATENCY: Duration = Duration::from_millis(2);
+    const MAX_BLOCKING_THREADS: usize = 64;
+
+    #[test]
+    fn threads() {
+        assert_eq!("blocking".parse(), Ok(Threads::Blocking));
+        assert_eq!("dedicated".parse(), Ok(Threads::Dedicated));
+        assert_eq!("16".parse(), Ok(Threads::Pool(16)));
+        assert!("0".parse::<Threads>().is_err());
+        assert!("all".parse::<Threads>().is_err());
+    }
+
+    fn elapsed(threads: Threads) -> Duration {
+        let runtime = Builder::new_multi_thread()
+            .worker_threads(4)
+            .max_blocking_threads(MAX_BLOCKING_THREADS)
+            .build()
+            .unwrap();
+        runtime.block_on(async {
+            let pool = match threads {
+                Threads::Pool(size) => Some(Worker::threads("pool", size).unwrap()),
+                _ => None,
+            };
+            let workers = (0..TERMINALS)
+                .map(|i| match threads {
+                    Threads::Blocking => Worker::Blocking,
+                    Threads::Dedicated => Worker::threads(&format!("terminal-{i}"), 1).unwrap(),
+                    Threads::Pool(_) => pool.clone().unwrap(),
+                })
+                .collect::<Vec<_>>();
+            let begin = Instant::now();
+            let terminals = workers
+                .into_iter()
+                .map(|worker| {
+                    tokio::spawn(async move {
+                        for _ in 0..CALLS {
+                            worker.run(|| thread::sleep(LATENCY)).await.unwrap();
+                        }
+                    })
+                })
+                .collect::<Vec<_>>();
+            for terminal in terminals {
+                terminal.await.unwrap();
+            }
+            begin.elapsed()
+        })
+    }
+
+    /// Run with `cargo test -p rsqlbench-yasdb --features dynamic --release -- --ignored --nocapture compare`.
+    #[test]
+    #[ignore = "timing comparison, not a check"]
+    fn compare() {
+        let ideal = LATENCY * CALLS as u32;
+        println!(
+            "{TERMINALS} terminals x {CALLS} calls of {LATENCY:?}, max_blocking_threads={MAX_BLOCKING_THREADS}, ideal {ideal:?}"
+        );
+        for threads in [
+            Threads::Blocking,
+            Threads::Dedicated,
+            Threads::Pool(64),
+            Threads::Pool(TERMINALS),
+        ] {
+            let elapsed = elapsed(threads);
+            let calls = (TERMINALS * CALLS) as f64 / elapsed.as_secs_f64();
+            println!("{threads:?}: {elapsed:?}, {calls:.0} calls/s");
+        }
+    }
+}
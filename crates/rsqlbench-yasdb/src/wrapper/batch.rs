@@ -0,0 +1,192 @@
+use std::{
+    cell::Cell,
+    mem::size_of,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    guard_yac_call,
+    native::{yacExecute, yacSetStmtAttr, EnYacStmtAttr_YAC_ATTR_PARAMSET_SIZE, YacInt32},
+};
+
+use super::{Binder, Connection, Error, Prepared, Value};
+
+/// Indicator of `NULL` value.
+const NULL_DATA: YacInt32 = -1;
+
+/// Values of one parameter for every row of a batch.
+///
+/// Size of buffers is fixed once created, and rows are set through shared references, so that buffers bound to
+/// driver are never moved or freed by filling rows.
+pub struct Column<T> {
+    values: Box<[Cell<T>]>,
+    lengths: Box<[Cell<YacInt32>]>,
+}
+
+impl<T: Value + Default + Copy> Column<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            values: (0..capacity).map(|_| Cell::default()).collect(),
+            lengths: (0..capacity).map(|_| Cell::new(NULL_DATA)).collect(),
+        }
+    }
+}
+
+impl<T: Value> Column<T> {
+    /// Set value of row, panics if row is out of capacity.
+    pub fn set(&self, row: usize, value: T) {
+        self.lengths[row].set(value.length());
+        self.values[row].set(value);
+    }
+
+    pub fn set_null(&self, row: usize) {
+        self.lengths[row].set(NULL_DATA);
+    }
+
+    /// Count of rows, which is the capacity created with.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub(super) fn buffers(&mut self) -> (*mut T, *mut YacInt32) {
+        // `Cell<T>` has the same memory layout as `T`.
+        (
+            self.values.as_mut_ptr() as *mut T,
+            self.lengths.as_mut_ptr() as *mut YacInt32,
+        )
+    }
+}
+
+/// Rows bound to columns of a prepared `INSERT` and inserted by one execution.
+pub struct Batch<C> {
+    prepared: Prepared<C>,
+    capacity: usize,
+    rows: usize,
+}
+
+impl<C> Batch<C> {
+    /// Prepare SQL and bind arrays of `columns`, which blocks until prepared.
+    ///
+    /// Every bound column must hold `capacity` rows at least, or [`Error::ShortArray`] is returned.
+    pub fn new(
+        conn: Arc<Mutex<Connection>>,
+        sql: &str,
+        capacity: usize,
+        columns: C,
+        bind: impl for<'p> FnOnce(&mut Binder<'p>, &'p mut C) -> Result<(), Error>,
+    ) -> Result<Self, Error> {
+        let capacity = capacity.max(1);
+        Ok(Self {
+            prepared: Prepared::with_rows(conn, sql, columns, capacity, bind)?,
+            capacity,
+            rows: 0,
+        })
+    }
+
+    /// Fill next row by `fill(columns, row)`, rows are inserted once batch is full, which blocks until inserted.
+    pub fn push(&mut self, fill: impl FnOnce(&C, usize)) -> Result<(), Error> {
+        fill(&self.prepared.params, self.rows);
+        self.rows += 1;
+        if self.rows >= self.capacity {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Insert filled rows, nothing happens if there are no rows.
+    pub fn flush(&mut self) -> Result<(), Error> {
+        if self.rows == 0 {
+            return Ok(());
+        }
+        let handle = self.prepared.stmt.handle().0;
+        let mut rows = self.rows as u32;
+        self.rows = 0;
+        unsafe {
+            guard_yac_call!(yacSetStmtAttr(
+                handle,
+                EnYacStmtAttr_YAC_ATTR_PARAMSET_SIZE,
+                &mut rows as *mut u32 as _,
+                size_of::<u32>() as _
+            ))?;
+            guard_yac_call!(yacExecute(handle), self.prepared.sql())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        native::{
+            mock::{mock, Call},
+            EnYacStmtAttr_YAC_ATTR_PARAMSET_SIZE,
+        },
+        wrapper::{Connection, Error},
+    };
+
+    use super::{Batch, Column};
+
+    struct Ids {
+        id: Column<u32>,
+    }
+
+    #[test]
+    fn batch() {
+        let mock = mock();
+        let conn = Connection::connect("127.0.0.1:1688", "sys", "yasdb").unwrap();
+        let mut batch = Batch::new(
+            Arc::new(Mutex::new(conn)),
+            "INSERT INTO T VALUES (?)",
+            2,
+            Ids { id: Column::new(2) },
+            |b, c| b.array(1, &mut c.id),
+        )
+        .unwrap();
+
+        batch.push(|c, row| c.id.set(row, 1)).unwrap();
+        batch.push(|c, row| c.id.set_null(row)).unwrap();
+        assert_eq!(unsafe { mock.input::<u32>(1, 0) }, Some(1));
+        assert_eq!(unsafe { mock.input::<u32>(1, 1) }, None);
+
+        batch.push(|c, row| c.id.set(row, 5)).unwrap();
+        batch.flush().unwrap();
+        batch.flush().unwrap();
+        assert_eq!(unsafe { mock.input::<u32>(1, 0) }, Some(5));
+
+        let executions = mock
+            .calls()
+            .into_iter()
+            .filter(|c| matches!(c, Call::SetStmtAttr(..) | Call::Execute))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            executions,
+            [
+                Call::SetStmtAttr(EnYacStmtAttr_YAC_ATTR_PARAMSET_SIZE, 2),
+                Call::Execute,
+                Call::SetStmtAttr(EnYacStmtAttr_YAC_ATTR_PARAMSET_SIZE, 1),
+                Call::Execute,
+            ]
+        );
+    }
+
+    #[test]
+    fn short_column() {
+        let _mock = mock();
+        let conn = Connection::connect("127.0.0.1:1688", "sys", "yasdb").unwrap();
+        let batch = Batch::new(
+            Arc::new(Mutex::new(conn)),
+            "INSERT INTO T VALUES (?)",
+            3,
+            Ids { id: Column::new(2) },
+            |b, c| b.array(1, &mut c.id),
+        );
+        assert!(matches!(
+            batch,
+            Err(Error::ShortArray {
+                rows: 2,
+                capacity: 3
+            })
+        ));
+    }
+}
@@ -0,0 +1,44 @@
+use crate::{
+    guard_yac_call,
+    native::{yacCommit, yacConnect, yacRollback},
+};
+
+use super::{DbcHandle, EnvHandle, Error};
+
+pub struct Connection {
+    pub(super) conn_handle: DbcHandle,
+    _env_handle: EnvHandle, // must be dropped after `conn_handle`
+}
+
+unsafe impl Send for Connection {}
+
+impl Connection {
+    /// Connect to database, which blocks until connected.
+    pub fn connect(url: &str, username: &str, password: &str) -> Result<Self, Error> {
+        let env_handle = EnvHandle::new()?.with_utf8();
+        let conn_handle = DbcHandle::new(&env_handle)?;
+        unsafe {
+            guard_yac_call!(yacConnect(
+                conn_handle.0,
+                url.as_ptr() as _,
+                url.len() as _,
+                username.as_ptr() as _,
+                username.len() as _,
+                password.as_ptr() as _,
+                password.len() as _,
+            ))?
+        };
+        Ok(Self {
+            conn_handle,
+            _env_handle: env_handle,
+        })
+    }
+
+    pub fn commit(&mut self) -> Result<(), Error> {
+        unsafe { guard_yac_call!(yacCommit(self.conn_handle.0)) }
+    }
+
+    pub fn rollback(&mut self) -> Result<(), Error> {
+        unsafe { guard_yac_call!(yacRollback(self.conn_handle.0)) }
+    }
+}
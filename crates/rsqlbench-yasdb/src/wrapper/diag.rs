@@ -1,16 +1,37 @@
-use std::{ffi::CStr, ptr::null_mut};
+use std::{ffi::CStr, fmt::Display, ptr::null_mut};
 
 use crate::native::{yacGetDiagRec, EnYacResult_YAC_ERROR, YacTextPos};
 
-#[derive(Debug)]
+use super::error::in_sql;
+
+/// Diagnostics of the last failed call, see [`super::Error`].
+#[derive(Debug, Clone)]
 pub struct DiagInfo {
-    _message: String,
-    _sql_state: String,
+    pub message: String,
+    pub sql_state: String,
     pub code: i32,
-    _pos: (i32, i32),
-    _sql: Option<String>,
+    /// Line and column in SQL, which are not positive if unknown.
+    pub pos: (i32, i32),
+    pub sql: Option<String>,
+}
+
+impl Display for DiagInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "YAS-{:05} {}{}",
+            self.code,
+            self.message,
+            in_sql(&self.sql)
+        )?;
+        if self.pos.0 > 0 && self.pos.1 > 0 {
+            write!(f, ", at [{}:{}]", self.pos.0, self.pos.1)?;
+        }
+        Ok(())
+    }
 }
 
+/// Get diagnostics of the last failed call, `None` if there are none.
 pub fn get_error(sql: Option<String>) -> Option<DiagInfo> {
     const BUFFER_LEN: usize = 4096;
     let message = [0u8; BUFFER_LEN];
@@ -33,38 +54,105 @@ pub fn get_error(sql: Option<String>) -> Option<DiagInfo> {
             )
         }
     {
-        None
-    } else {
-        let message = CStr::from_bytes_until_nul(&message[..])
-            .ok()?
-            .to_str()
-            .ok()?
-            .to_string();
-        let lines = message.lines().collect::<Vec<_>>();
-        let (message, pos) = if matches!(lines.first(), Some(&"PL/SQL compiling errors:")) {
-            let (pos, message) = lines.get(1).unwrap().split_once(' ').unwrap();
-            let pos = pos
-                .trim_start_matches('[')
-                .trim_end_matches(']')
-                .split_once(':')
-                .unwrap();
-            let pos = (pos.0.parse::<i32>().unwrap(), pos.1.parse::<i32>().unwrap());
-            (message.to_string(), pos)
-        } else {
-            (message, (pos.line, pos.column))
-        };
+        return None;
+    }
+    let message = text(&message);
+    let (message, pos) =
+        compiling_error(&message).unwrap_or((message.clone(), (pos.line, pos.column)));
+    Some(DiagInfo {
+        message,
+        sql_state: text(&sql_state),
+        code: err_code,
+        pos,
+        sql,
+    })
+}
+
+fn text(buffer: &[u8]) -> String {
+    match CStr::from_bytes_until_nul(buffer) {
+        Ok(text) => text.to_string_lossy().into_owned(),
+        Err(_) => String::from_utf8_lossy(buffer).into_owned(),
+    }
+}
+
+/// Message and position of the first error of PL/SQL compiling errors like:
+///
+/// ```text
+/// PL/SQL compiling errors:
+/// [3:5] identifier D_TAX must be declared
+/// ```
+fn compiling_error(message: &str) -> Option<(String, (i32, i32))> {
+    let mut lines = message.lines();
+    if lines.next()? != "PL/SQL compiling errors:" {
+        return None;
+    }
+    let (pos, message) = lines.next()?.split_once(' ')?;
+    let (line, column) = pos.strip_prefix('[')?.strip_suffix(']')?.split_once(':')?;
+    Some((
+        message.to_string(),
+        (line.parse().ok()?, column.parse().ok()?),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::native::mock::{mock, Diag};
+
+    use super::get_error;
+
+    #[test]
+    fn no_error() {
+        let _mock = mock();
+        assert!(get_error(None).is_none());
+    }
+
+    #[test]
+    fn error() {
+        let mock = mock();
+        mock.set_diag(Some(Diag {
+            code: 2023,
+            message: "deadlock detected".to_string(),
+            sql_state: "40001".to_string(),
+            pos: (1, 7),
+        }));
+        let e = get_error(Some("CALL DELIVERY(?, ?, now())".to_string())).unwrap();
+        assert_eq!(e.code, 2023);
+        assert_eq!(e.message, "deadlock detected");
+        assert_eq!(e.sql_state, "40001");
+        assert_eq!(e.pos, (1, 7));
+        assert_eq!(e.sql.as_deref(), Some("CALL DELIVERY(?, ?, now())"));
+    }
+
+    #[test]
+    fn compiling_error() {
+        let mock = mock();
+        mock.set_diag(Some(Diag {
+            pos: (1, 1),
+            ..Diag::new(
+                1504,
+                "PL/SQL compiling errors:\n[3:5] identifier D_TAX must be declared",
+            )
+        }));
+        let e = get_error(None).unwrap();
+        assert_eq!(e.code, 1504);
+        assert_eq!(e.message, "identifier D_TAX must be declared");
+        assert_eq!(e.pos, (3, 5));
+    }
+
+    #[test]
+    fn malformed_compiling_error() {
+        let mock = mock();
+        let message = "PL/SQL compiling errors:\n[3] identifier D_TAX must be declared";
+        mock.set_diag(Some(Diag::new(1504, message)));
+        let e = get_error(None).unwrap();
+        assert_eq!(e.message, message);
+        assert_eq!(e.pos, (-1, -1));
+    }
 
-        let sql_state = CStr::from_bytes_until_nul(&sql_state[..])
-            .ok()?
-            .to_str()
-            .ok()?
-            .to_string();
-        Some(DiagInfo {
-            _message: message,
-            _sql_state: sql_state,
-            code: err_code,
-            _pos: pos,
-            _sql: sql,
-        })
+    #[test]
+    fn truncated_message() {
+        let mock = mock();
+        mock.set_diag(Some(Diag::new(1, &"x".repeat(5000))));
+        assert_eq!(get_error(None).unwrap().message.len(), 4095);
     }
 }
@@ -1,17 +1,138 @@
 use super::diag::{get_error, DiagInfo};
 
+/// Snapshot for consistent read is overwritten, such as undo is recycled during a long query.
+pub const SNAPSHOT_TOO_OLD: i32 = 2020;
+
+/// Deadlock detected while waiting for a lock.
+pub const DEADLOCK: i32 = 2023;
+
+/// No data found, such as customer selected by last name does not exist.
+pub const NO_DATA_FOUND: i32 = 5206;
+
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
-    #[error("{0:?}")]
+    #[error("{0}")]
     YasClient(DiagInfo),
-    // #[error(transparent)]
-    // Other(#[from] Box<dyn std::error::Error>),
-    // #[error("Unknown error")]
-    // Other,
+
+    #[error("YashanDB client failed without diagnostics{}", in_sql(.sql))]
+    NoDiagnostics { sql: Option<String> },
+
+    #[error("Array of {rows} rows is bound to batch of {capacity} rows")]
+    ShortArray { rows: usize, capacity: usize },
+}
+
+/// Suffix of message showing failed SQL if any.
+pub(super) fn in_sql(sql: &Option<String>) -> String {
+    sql.as_ref()
+        .map(|sql| format!(", SQL: {sql}"))
+        .unwrap_or_default()
+}
+
+/// Category of error, mapped from error code or class of SQL state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Deadlock,
+    SnapshotTooOld,
+    NoDataFound,
+    /// Transaction rollback such as serialization failure, SQL state class `40`.
+    SerializationFailure,
+    /// Integrity constraint violation, SQL state class `23`.
+    ConstraintViolation,
+    /// Connection exception, SQL state class `08`.
+    ConnectionLost,
+    Other,
 }
 
 impl Error {
-    pub fn get_yas_diag(sql: Option<String>) -> Option<Error> {
-        get_error(sql).map(Error::YasClient)
+    /// Error of the last failed call, which is [`Error::NoDiagnostics`] if driver reports nothing.
+    pub fn last(sql: Option<String>) -> Self {
+        match get_error(sql.clone()) {
+            Some(diag) => Error::YasClient(diag),
+            None => Error::NoDiagnostics { sql },
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        let Error::YasClient(diag) = self else {
+            return ErrorKind::Other;
+        };
+        match diag.code {
+            DEADLOCK => return ErrorKind::Deadlock,
+            SNAPSHOT_TOO_OLD => return ErrorKind::SnapshotTooOld,
+            NO_DATA_FOUND => return ErrorKind::NoDataFound,
+            _ => {}
+        }
+        match diag.sql_state.get(..2) {
+            Some("40") => ErrorKind::SerializationFailure,
+            Some("23") => ErrorKind::ConstraintViolation,
+            Some("08") => ErrorKind::ConnectionLost,
+            _ => ErrorKind::Other,
+        }
+    }
+
+    /// Transaction is rolled back because of conflicts and could be retried, see
+    /// [`rsqlbench_core::tpcc::sut::Aborted`].
+    pub fn is_aborted(&self) -> bool {
+        matches!(
+            self.kind(),
+            ErrorKind::Deadlock | ErrorKind::SerializationFailure
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::native::mock::{mock, Diag};
+
+    use super::{Error, ErrorKind, DEADLOCK, NO_DATA_FOUND, SNAPSHOT_TOO_OLD};
+
+    fn last(diag: Diag) -> Error {
+        let mock = mock();
+        mock.set_diag(Some(diag));
+        Error::last(Some("CALL DELIVERY(?, ?, now())".to_string()))
+    }
+
+    #[test]
+    fn kind() {
+        for (code, sql_state, kind) in [
+            (DEADLOCK, "", ErrorKind::Deadlock),
+            (SNAPSHOT_TOO_OLD, "", ErrorKind::SnapshotTooOld),
+            (NO_DATA_FOUND, "02000", ErrorKind::NoDataFound),
+            (4, "40001", ErrorKind::SerializationFailure),
+            (1, "23000", ErrorKind::ConstraintViolation),
+            (2, "08006", ErrorKind::ConnectionLost),
+            (3, "42000", ErrorKind::Other),
+        ] {
+            let e = last(Diag {
+                sql_state: sql_state.to_string(),
+                ..Diag::new(code, "")
+            });
+            assert_eq!(e.kind(), kind, "{code} {sql_state}");
+            assert_eq!(
+                e.is_aborted(),
+                matches!(kind, ErrorKind::Deadlock | ErrorKind::SerializationFailure)
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let e = last(Diag {
+            pos: (1, 7),
+            ..Diag::new(DEADLOCK, "deadlock detected")
+        });
+        assert_eq!(
+            e.to_string(),
+            "YAS-02023 deadlock detected, SQL: CALL DELIVERY(?, ?, now()), at [1:7]"
+        );
+    }
+
+    #[test]
+    fn no_diagnostics() {
+        let _mock = mock();
+        let e = Error::last(None);
+        assert!(matches!(e, Error::NoDiagnostics { sql: None }));
+        assert_eq!(e.kind(), ErrorKind::Other);
+        assert_eq!(e.to_string(), "YashanDB client failed without diagnostics");
     }
 }
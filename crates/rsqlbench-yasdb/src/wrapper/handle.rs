@@ -12,7 +12,7 @@ use super::Error;
 macro_rules! handle {
     ($name:ident => $handle_type:expr; $($input:ty)?) => {
         #[derive(Debug)]
-        pub struct $name(pub YacHandle);
+        pub struct $name(pub(super) YacHandle);
 
         unsafe impl Send for $name {}
 
@@ -36,7 +36,7 @@ macro_rules! handle {
                 if result != EnYacResult_YAC_ERROR {
                     Ok(Self(handle))
                 } else {
-                    Err(Error::get_yas_diag(None).unwrap())
+                    Err(Error::last(None))
                 }
             }
         }
@@ -1,8 +1,14 @@
+mod batch;
+mod conn;
 mod diag;
 mod error;
 mod handle;
+mod prepared;
 mod stmt;
 
+pub use batch::*;
+pub use conn::*;
 pub use error::*;
-pub use handle::*;
+use handle::*;
+pub use prepared::*;
 pub use stmt::*;
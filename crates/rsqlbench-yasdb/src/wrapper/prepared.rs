@@ -0,0 +1,385 @@
+use std::{
+    marker::PhantomData,
+    mem::size_of,
+    ptr::null_mut,
+    sync::{Arc, Mutex},
+};
+
+use crate::{
+    guard_yac_call,
+    native::{
+        yacBindColumn, yacBindParameter, yacExecute, yacFetch, yacPrepare,
+        EnYacExtType_YAC_SQLT_DATE, EnYacExtType_YAC_SQLT_FLOAT, EnYacExtType_YAC_SQLT_INTEGER,
+        EnYacExtType_YAC_SQLT_VARCHAR2, EnYacParamDirection_YAC_PARAM_INPUT,
+        EnYacParamDirection_YAC_PARAM_OUTPUT, YacHandle, YacInt32, YacParamDirection, YacUint16,
+        YacUint32,
+    },
+};
+
+use super::{Column, Connection, Error, Statement};
+
+/// Value bound to parameters or columns, whose memory is read and written by driver directly.
+///
+/// # Safety
+///
+/// `TYPE` must match memory layout of the implementor, which is passed to driver with its size.
+pub unsafe trait Value: Sized {
+    const TYPE: YacUint32;
+
+    /// Length of value in bytes, passed to driver when bound as array.
+    fn length(&self) -> YacInt32 {
+        size_of::<Self>() as _
+    }
+}
+
+unsafe impl Value for u32 {
+    const TYPE: YacUint32 = EnYacExtType_YAC_SQLT_INTEGER;
+}
+
+unsafe impl Value for f32 {
+    const TYPE: YacUint32 = EnYacExtType_YAC_SQLT_FLOAT;
+}
+
+/// `VARCHAR2` in buffer of `N` bytes padded with `\0`.
+#[derive(Debug, Clone, Copy)]
+#[repr(transparent)]
+pub struct Text<const N: usize>([u8; N]);
+
+unsafe impl<const N: usize> Value for Text<N> {
+    const TYPE: YacUint32 = EnYacExtType_YAC_SQLT_VARCHAR2;
+
+    fn length(&self) -> YacInt32 {
+        self.0.iter().position(|&b| b == 0).unwrap_or(N) as _
+    }
+}
+
+impl<const N: usize> Default for Text<N> {
+    fn default() -> Self {
+        Self([0; N])
+    }
+}
+
+impl<const N: usize> From<&str> for Text<N> {
+    /// Text longer than buffer is truncated.
+    fn from(text: &str) -> Self {
+        let mut buffer = [0; N];
+        let len = text.len().min(N);
+        buffer[..len].copy_from_slice(&text.as_bytes()[..len]);
+        Self(buffer)
+    }
+}
+
+impl<const N: usize> std::fmt::Display for Text<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let end = self.0.iter().position(|&b| b == 0).unwrap_or(N);
+        f.write_str(&String::from_utf8_lossy(&self.0[..end]))
+    }
+}
+
+/// `DATE` in driver's internal format, it's opaque for now.
+#[derive(Debug, Default, Clone, Copy)]
+#[repr(transparent)]
+pub struct Date([u8; 20]);
+
+unsafe impl Value for Date {
+    const TYPE: YacUint32 = EnYacExtType_YAC_SQLT_DATE;
+}
+
+/// Binds parameters and columns to fields of `P` in [`Prepared::new`].
+///
+/// Only values living as long as `'p`, which are fields of the boxed `P` owned by [`Prepared`], could be bound, so
+/// that addresses passed to driver stay valid until the statement is freed.
+pub struct Binder<'p> {
+    stmt: YacHandle,
+    /// Rows read by driver from every bound array on execution.
+    rows: usize,
+    _values: PhantomData<&'p mut ()>,
+}
+
+impl<'p> Binder<'p> {
+    pub fn input<T: Value>(&mut self, id: YacUint16, value: &'p mut T) -> Result<(), Error> {
+        self.parameter(id, EnYacParamDirection_YAC_PARAM_INPUT, value)
+    }
+
+    /// Output or input/output parameter, inputs are read from value before executing.
+    pub fn output<T: Value>(&mut self, id: YacUint16, value: &'p mut T) -> Result<(), Error> {
+        self.parameter(id, EnYacParamDirection_YAC_PARAM_OUTPUT, value)
+    }
+
+    /// Column of result set, which is written on every fetch.
+    pub fn column<T: Value>(&mut self, id: YacUint16, value: &'p mut T) -> Result<(), Error> {
+        unsafe {
+            guard_yac_call!(yacBindColumn(
+                self.stmt,
+                id,
+                T::TYPE,
+                value as *mut T as _,
+                size_of::<T>() as _,
+                null_mut()
+            ))
+        }
+    }
+
+    /// Input parameter bound to every row of column, see [`super::Batch`].
+    ///
+    /// Column must hold rows executed at once, so that driver never reads beyond its buffers.
+    pub fn array<T: Value>(
+        &mut self,
+        id: YacUint16,
+        column: &'p mut Column<T>,
+    ) -> Result<(), Error> {
+        if column.len() < self.rows {
+            return Err(Error::ShortArray {
+                rows: column.len(),
+                capacity: self.rows,
+            });
+        }
+        let (values, lengths) = column.buffers();
+        unsafe {
+            guard_yac_call!(yacBindParameter(
+                self.stmt,
+                id,
+                EnYacParamDirection_YAC_PARAM_INPUT,
+                T::TYPE,
+                values as _,
+                size_of::<T>() as _,
+                size_of::<T>() as _,
+                lengths
+            ))
+        }
+    }
+
+    fn parameter<T: Value>(
+        &mut self,
+        id: YacUint16,
+        direction: YacParamDirection,
+        value: &'p mut T,
+    ) -> Result<(), Error> {
+        unsafe {
+            guard_yac_call!(yacBindParameter(
+                self.stmt,
+                id,
+                direction,
+                T::TYPE,
+                value as *mut T as _,
+                size_of::<T>() as _,
+                size_of::<T>() as _,
+                null_mut()
+            ))
+        }
+    }
+}
+
+/// Statement prepared once and executed many times.
+///
+/// Parameters and columns are bound to fields of `params` once, which is boxed so that addresses of fields never
+/// change. Inputs are written into `params` before every execution, and outputs are read from it after, which
+/// requires `P: Copy` so that overwriting `params` never frees buffers bound to driver.
+pub struct Prepared<P> {
+    pub(super) stmt: Statement, // must be dropped before `params`
+    pub(super) params: Box<P>,
+    pub(super) sql: String,
+}
+
+impl<P: Default + Copy> Prepared<P> {
+    /// Prepare SQL and bind fields of `P`, which blocks until prepared.
+    pub fn new(
+        conn: Arc<Mutex<Connection>>,
+        sql: &str,
+        bind: impl for<'p> FnOnce(&mut Binder<'p>, &'p mut P) -> Result<(), Error>,
+    ) -> Result<Self, Error> {
+        Self::with_params(conn, sql, P::default(), bind)
+    }
+
+    /// Reset outputs, set inputs and execute, which blocks until executed.
+    pub fn execute(&mut self, params: P) -> Result<(), Error> {
+        *self.params = params;
+        unsafe { guard_yac_call!(yacExecute(self.stmt.handle().0), self.sql()) }
+    }
+
+    /// Execute and iterate rows of result set, which block until executed or fetched.
+    pub fn query(&mut self, params: P) -> Result<Rows<'_, P>, Error> {
+        self.execute(params)?;
+        Ok(Rows { prepared: self })
+    }
+}
+
+impl<P> Prepared<P> {
+    /// Prepare SQL and bind fields of initial `params`, which blocks until prepared.
+    pub fn with_params(
+        conn: Arc<Mutex<Connection>>,
+        sql: &str,
+        params: P,
+        bind: impl for<'p> FnOnce(&mut Binder<'p>, &'p mut P) -> Result<(), Error>,
+    ) -> Result<Self, Error> {
+        Self::with_rows(conn, sql, params, 1, bind)
+    }
+
+    /// Prepare SQL and bind fields of initial `params`, bound arrays must hold `rows` executed at once.
+    pub(super) fn with_rows(
+        conn: Arc<Mutex<Connection>>,
+        sql: &str,
+        params: P,
+        rows: usize,
+        bind: impl for<'p> FnOnce(&mut Binder<'p>, &'p mut P) -> Result<(), Error>,
+    ) -> Result<Self, Error> {
+        let stmt = Statement::new(conn)?;
+        unsafe {
+            guard_yac_call!(
+                yacPrepare(stmt.handle().0, sql.as_ptr() as _, sql.len() as _),
+                Some(sql.to_string())
+            )?
+        };
+        let mut params = Box::new(params);
+        let mut binder = Binder {
+            stmt: stmt.handle().0,
+            rows,
+            _values: PhantomData,
+        };
+        bind(&mut binder, &mut params)?;
+        Ok(Self {
+            stmt,
+            params,
+            sql: sql.to_string(),
+        })
+    }
+
+    /// Parameters and columns after the last execution or fetch.
+    pub fn params(&self) -> &P {
+        &self.params
+    }
+
+    /// SQL shown in errors.
+    pub(super) fn sql(&self) -> Option<String> {
+        Some(self.sql.clone())
+    }
+}
+
+/// Rows fetched into columns, every row is copied out of bound fields.
+pub struct Rows<'a, P> {
+    prepared: &'a mut Prepared<P>,
+}
+
+impl<P: Clone> Iterator for Rows<'_, P> {
+    type Item = Result<P, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut rows: YacUint32 = 0;
+        let fetched = unsafe {
+            guard_yac_call!(
+                yacFetch(self.prepared.stmt.handle().0, &mut rows),
+                self.prepared.sql()
+            )
+        };
+        match fetched {
+            Ok(()) if rows == 0 => None,
+            Ok(()) => Some(Ok((*self.prepared.params).clone())),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{Arc, Mutex};
+
+    use crate::{
+        native::{
+            mock::{mock, Call, Diag},
+            EnYacExtType_YAC_SQLT_INTEGER, EnYacParamDirection_YAC_PARAM_OUTPUT,
+        },
+        wrapper::{Connection, Error},
+    };
+
+    use super::{Prepared, Text};
+
+    #[derive(Debug, Default, Clone, Copy)]
+    struct Params {
+        id: u32,
+        name: Text<8>,
+        count: u32,
+    }
+
+    fn prepare() -> Prepared<Params> {
+        let conn = Connection::connect("127.0.0.1:1688", "sys", "yasdb").unwrap();
+        Prepared::new(
+            Arc::new(Mutex::new(conn)),
+            "CALL P(?, ?, ?)",
+            |b, p: &mut Params| {
+                b.input(1, &mut p.id)?;
+                b.input(2, &mut p.name)?;
+                b.output(3, &mut p.count)
+            },
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn execute() {
+        let mock = mock();
+        let mut prepared = prepare();
+        mock.output(3, 42u32);
+        prepared
+            .execute(Params {
+                id: 7,
+                name: "abc".into(),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(prepared.params().count, 42);
+        assert_eq!(unsafe { mock.input::<u32>(1, 0) }, Some(7));
+        assert_eq!(
+            unsafe { mock.input::<Text<8>>(2, 0) }.unwrap().to_string(),
+            "abc"
+        );
+
+        let calls = mock.calls();
+        assert!(calls.contains(&Call::Connect {
+            url: "127.0.0.1:1688".to_string(),
+            user: "sys".to_string(),
+            password: "yasdb".to_string(),
+        }));
+        assert!(calls.contains(&Call::Prepare("CALL P(?, ?, ?)".to_string())));
+        assert!(calls.contains(&Call::BindParameter {
+            id: 3,
+            direction: EnYacParamDirection_YAC_PARAM_OUTPUT,
+            type_: EnYacExtType_YAC_SQLT_INTEGER,
+        }));
+        assert_eq!(calls.last(), Some(&Call::Execute));
+    }
+
+    #[test]
+    fn execute_failed() {
+        let mock = mock();
+        let mut prepared = prepare();
+        mock.fail("yacExecute", Diag::new(2023, "deadlock detected"));
+        match prepared.execute(Params::default()) {
+            Err(Error::YasClient(e)) => assert_eq!(e.code, 2023),
+            r => panic!("Expected error, found {r:?}"),
+        }
+        assert!(prepared.execute(Params::default()).is_ok());
+    }
+
+    #[test]
+    fn query() {
+        let mock = mock();
+        let conn = Connection::connect("127.0.0.1:1688", "sys", "yasdb").unwrap();
+        let mut prepared = Prepared::new(
+            Arc::new(Mutex::new(conn)),
+            "SELECT COUNT(*) FROM T WHERE ID > ?",
+            |b, p: &mut Params| {
+                b.input(1, &mut p.id)?;
+                b.column(1, &mut p.count)
+            },
+        )
+        .unwrap();
+        mock.rows(1, &[3u32, 5]);
+        let counts = prepared
+            .query(Params::default())
+            .unwrap()
+            .map(|p| p.map(|p| p.count))
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(counts, [3, 5]);
+    }
+}
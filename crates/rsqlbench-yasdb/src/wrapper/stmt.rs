@@ -3,12 +3,9 @@ use std::sync::{Arc, Mutex};
 use rsqlbench_core::tpcc::sut::generic_direct::Executor;
 use tokio::task::spawn_blocking;
 
-use crate::{
-    native::{yacDirectExecute, EnYacResult_YAC_ERROR},
-    Connection,
-};
+use crate::native::{yacDirectExecute, EnYacResult_YAC_ERROR};
 
-use super::{Error, StatementHandle};
+use super::{Connection, Error, StatementHandle};
 
 pub struct Statement {
     stmt: StatementHandle,
@@ -26,7 +23,7 @@ impl Statement {
         })
     }
 
-    pub fn handle(&self) -> &StatementHandle {
+    pub(super) fn handle(&self) -> &StatementHandle {
         &self.stmt
     }
 }
@@ -52,7 +49,7 @@ impl Executor for SimpleExecutor {
             if unsafe { yacDirectExecute(stmt.stmt.0, sql.as_ptr() as _, sql.len() as _) }
                 == EnYacResult_YAC_ERROR
             {
-                Err(Error::get_yas_diag(Some(sql.to_string())).unwrap().into())
+                Err(Error::last(Some(sql)).into())
             } else {
                 Ok(())
             }
@@ -61,11 +58,15 @@ impl Executor for SimpleExecutor {
     }
 }
 
+/// Turn failed call into [`Error::last`], with failed SQL if given.
 #[macro_export]
 macro_rules! guard_yac_call {
     ($call:expr) => {
+        $crate::guard_yac_call!($call, None)
+    };
+    ($call:expr, $sql:expr) => {
         if $call == $crate::native::EnYacResult_YAC_ERROR {
-            Err($crate::wrapper::Error::get_yas_diag(None).unwrap())
+            Err($crate::wrapper::Error::last($sql))
         } else {
             Ok(())
         }
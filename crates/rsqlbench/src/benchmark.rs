@@ -1,4 +1,12 @@
-use std::{collections::HashMap, rc::Rc, time::Duration};
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use anyhow::anyhow;
 use case_style::CaseStyle;
@@ -8,27 +16,31 @@ use rsqlbench_core::{
         tpcc::{TpccBenchmark, TpccTransaction},
     },
     tpcc::{
-        model::DISTRICT_PER_WAREHOUSE,
-        sut::{Sut, Terminal},
+        model::{ScaleModel, DISTRICT_PER_WAREHOUSE},
+        sut::{Aborted, Sut, Terminal},
         transaction::Transaction,
     },
 };
 use tokio::{
     select,
+    signal::ctrl_c,
     sync::broadcast,
     task::{yield_now, JoinSet},
-    time::{interval_at, sleep, Instant},
+    time::{interval_at, sleep, sleep_until, Instant},
 };
 
 use tracing::{debug, error, info, instrument, trace, warn};
 
-use crate::monitor::{TPM_NEW_ORDER, TPM_TOTAL, TX_NEW_ORDER, TX_TOTAL};
+use crate::{
+    control::{Command, Control, Phase},
+    monitor::{TPM_NEW_ORDER, TPM_TOTAL, TX_ABORTED, TX_NEW_ORDER, TX_TOTAL},
+};
 
 // static TOTAL_NEW_ORDERS: AtomicU64 = AtomicU64::new(0);
 // static TOTAL_TRANSACTIONS: AtomicU64 = AtomicU64::new(0);
 
 #[allow(clippy::too_many_arguments)] // TODO
-#[instrument(skip(terminal, rx_stop))]
+#[instrument(skip(terminal, rx_stop, retired, control))]
 async fn tpcc_benchmark(
     terminal: Box<dyn Terminal>,
     terminal_id: usize,
@@ -36,56 +48,83 @@ async fn tpcc_benchmark(
     district_id: u8,
     warehouse_count: u32,
     tx_weights: TpccTransaction,
+    scale: ScaleModel,
     keying: bool,
     rx_stop: broadcast::Receiver<()>,
+    retired: Arc<AtomicBool>,
+    control: Arc<Control>,
 ) -> anyhow::Result<()> {
     let mut rx_stop = rx_stop;
     let mut terminal = terminal;
     trace!("Begin benchmarking");
     loop {
-        let tx = Transaction::generate(&tx_weights, warehouse_id, district_id, warehouse_count);
-        if rx_stop.try_recv().is_ok() {
+        let tx = Transaction::generate(
+            &tx_weights,
+            &scale,
+            warehouse_id,
+            district_id,
+            warehouse_count,
+        );
+        if rx_stop.try_recv().is_ok() || retired.load(Ordering::Relaxed) {
             break;
         }
         if keying {
-            sleep(tx.keying_duration()).await;
+            select! {
+                _ = sleep(tx.keying_duration()) => {}
+                _ = rx_stop.recv() => break,
+            }
         }
+        control.throttle.acquire().await;
         debug!(?tx, "Perform transaction");
-        match &tx {
-            Transaction::NewOrder(input) => {
-                trace!(%input);
-                match terminal.new_order(input).await? {
-                    Ok(out) => trace!(%out, "New order created"),
-                    Err(rb) => trace!(%rb, "Failed to create new order"),
+        let performed = async {
+            match &tx {
+                Transaction::NewOrder(input) => {
+                    trace!(%input);
+                    match terminal.new_order(input).await? {
+                        Ok(out) => trace!(%out, "New order created"),
+                        Err(rb) => trace!(%rb, "Failed to create new order"),
+                    }
+                    if !input.rollback_last {
+                        TX_NEW_ORDER.inc();
+                    }
                 }
-                if !input.rollback_last {
-                    TX_NEW_ORDER.inc();
+                Transaction::Payment(input) => {
+                    trace!(%input);
+                    let out = terminal.payment(input).await?;
+                    trace!(%out, "Paid");
+                }
+                Transaction::OrderStatus(input) => {
+                    trace!(%input);
+                    let out = terminal.order_status(input).await?;
+                    trace!(%out, "Query order status");
+                }
+                Transaction::Delivery(input) => {
+                    trace!(%input);
+                    let out = terminal.delivery(input).await?;
+                    trace!(%out, "Delivery orders");
+                }
+                Transaction::StockLevel(input) => {
+                    trace!(%input);
+                    let out = terminal.stock_level(input).await?;
+                    trace!(%out, "Query stock level");
                 }
             }
-            Transaction::Payment(input) => {
-                trace!(%input);
-                let out = terminal.payment(input).await?;
-                trace!(%out, "Paid");
-            }
-            Transaction::OrderStatus(input) => {
-                trace!(%input);
-                let out = terminal.order_status(input).await?;
-                trace!(%out, "Query order status");
-            }
-            Transaction::Delivery(input) => {
-                trace!(%input);
-                let out = terminal.delivery(input).await?;
-                trace!(%out, "Delivery orders");
-            }
-            Transaction::StockLevel(input) => {
-                trace!(%input);
-                let out = terminal.stock_level(input).await?;
-                trace!(%out, "Query stock level");
+            anyhow::Ok(())
+        }
+        .await;
+        match performed {
+            Ok(()) => TX_TOTAL.inc(),
+            Err(e) if e.is::<Aborted>() => {
+                debug!(%e, "Transaction aborted");
+                TX_ABORTED.inc();
             }
+            Err(e) => return Err(e),
         }
-        TX_TOTAL.inc();
         if keying {
-            sleep(tx.thinking_duration()).await;
+            select! {
+                _ = sleep(tx.thinking_duration()) => {}
+                _ = rx_stop.recv() => break,
+            }
         }
         yield_now().await;
     }
@@ -139,81 +178,231 @@ fn check_weight(tpcc: &TpccBenchmark, warehouses: usize) -> anyhow::Result<()> {
     Ok(())
 }
 
-async fn spawn_terminals(
+/// Terminals spawned for benchmarking, terminals could be added or retired on the fly.
+struct Terminals<'a> {
     warehouses: usize,
+    scale: ScaleModel,
     sut: Rc<Box<dyn Sut>>,
-    tpcc: &TpccBenchmark,
-    tx_stop: &broadcast::Sender<()>,
-) -> anyhow::Result<JoinSet<Result<(), anyhow::Error>>> {
-    let mut join_set = JoinSet::new();
-    for terminal_id in 0..tpcc.terminals {
-        let in_range_id = terminal_id % (warehouses * DISTRICT_PER_WAREHOUSE);
-        let warehouse_id = (in_range_id / DISTRICT_PER_WAREHOUSE) + 1;
-        let district_id = (in_range_id % DISTRICT_PER_WAREHOUSE) + 1;
-        join_set.spawn(tpcc_benchmark(
-            sut.terminal(terminal_id as _).await?,
-            terminal_id,
-            warehouse_id as u32,
-            district_id as u8,
-            warehouses as _,
-            tpcc.transactions.clone(),
-            tpcc.keying_and_thinking,
-            tx_stop.subscribe(),
-        ));
+    tpcc: &'a TpccBenchmark,
+    control: Arc<Control>,
+    tx_stop: broadcast::Sender<()>,
+    join_set: JoinSet<Result<(), anyhow::Error>>,
+    /// Retire flags of active terminals.
+    active: Vec<Arc<AtomicBool>>,
+    next_id: usize,
+}
+
+impl<'a> Terminals<'a> {
+    fn new(
+        warehouses: usize,
+        scale: ScaleModel,
+        sut: Rc<Box<dyn Sut>>,
+        tpcc: &'a TpccBenchmark,
+        control: Arc<Control>,
+    ) -> Self {
+        let (tx_stop, _) = broadcast::channel::<()>(1);
+        Self {
+            warehouses,
+            scale,
+            sut,
+            tpcc,
+            control,
+            tx_stop,
+            join_set: JoinSet::new(),
+            active: vec![],
+            next_id: 0,
+        }
+    }
+
+    async fn spawn(&mut self, count: usize) -> anyhow::Result<()> {
+        for _ in 0..count {
+            let terminal_id = self.next_id;
+            let in_range_id = terminal_id % (self.warehouses * DISTRICT_PER_WAREHOUSE);
+            let warehouse_id = (in_range_id / DISTRICT_PER_WAREHOUSE) + 1;
+            let district_id = (in_range_id % DISTRICT_PER_WAREHOUSE) + 1;
+            let retired = Arc::new(AtomicBool::new(false));
+            let mut terminal = self.sut.terminal(terminal_id as _).await?;
+            if let Some(level) = self.tpcc.isolation {
+                terminal.set_isolation(level).await?;
+            }
+            self.join_set.spawn(tpcc_benchmark(
+                terminal,
+                terminal_id,
+                warehouse_id as u32,
+                district_id as u8,
+                self.warehouses as _,
+                self.tpcc.transactions.clone(),
+                self.scale,
+                self.tpcc.keying_and_thinking,
+                self.tx_stop.subscribe(),
+                retired.clone(),
+                self.control.clone(),
+            ));
+            self.active.push(retired);
+            self.next_id += 1;
+        }
+        self.control.set_terminals(self.active.len());
+        Ok(())
+    }
+
+    /// Retire latest spawned terminals, they will exit after current transaction.
+    fn retire(&mut self, count: usize) {
+        for _ in 0..count {
+            if let Some(retired) = self.active.pop() {
+                retired.store(true, Ordering::Relaxed);
+            }
+        }
+        self.control.set_terminals(self.active.len());
+    }
+
+    async fn resize(&mut self, terminals: usize) -> anyhow::Result<()> {
+        let active = self.active.len();
+        if terminals > active {
+            info!(from = active, to = terminals, "Adding terminals");
+            self.spawn(terminals - active).await?;
+        } else if terminals < active {
+            info!(from = active, to = terminals, "Retiring terminals");
+            self.retire(active - terminals);
+        }
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        let _ = self.tx_stop.send(()); // terminals may have exited already
+        self.retire(self.active.len());
+    }
+}
+
+/// Wait for SIGINT(Ctrl-C) or SIGTERM.
+async fn shutdown_signal() -> &'static str {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        let mut terminate =
+            signal(SignalKind::terminate()).expect("Failed to listen SIGTERM signal");
+        select! {
+            _ = ctrl_c() => "SIGINT",
+            _ = terminate.recv() => "SIGTERM",
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = ctrl_c().await;
+        "Ctrl-C"
+    }
+}
+
+/// Result of benchmark(excluding ramp up).
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub new_orders: u64,
+    pub transactions: u64,
+    /// Minutes of baking, measured if interrupted.
+    pub minutes: f64,
+    pub tpmc_new_order: f64,
+    pub tpm_total: f64,
+    /// Transactions aborted by conflicts, which are not counted in `transactions`.
+    pub aborted: u64,
+    /// Why benchmark finished early.
+    pub interrupted: Option<&'static str>,
+}
+
+impl Summary {
+    /// Aborted transactions in all attempted ones.
+    pub fn abort_rate(&self) -> f64 {
+        match self.transactions + self.aborted {
+            0 => 0.0,
+            attempted => self.aborted as f64 / attempted as f64,
+        }
+    }
+}
+
+/// Snapshot of new orders, transactions and aborted transactions.
+fn counters() -> (u64, u64, u64) {
+    (TX_NEW_ORDER.get(), TX_TOTAL.get(), TX_ABORTED.get())
+}
+
+fn per_minute(count: u64, minutes: f64) -> f64 {
+    if minutes > 0.0 {
+        count as f64 / minutes
+    } else {
+        0.0
     }
-    Ok(join_set)
 }
 
 async fn wait_for_benchmark(
     tpcc: &TpccBenchmark,
-    join_set: JoinSet<Result<(), anyhow::Error>>,
-    tx_stop: broadcast::Sender<()>,
-) -> anyhow::Result<()> {
-    let mut join_set = join_set;
-    const GATHER_INTERVAL_SECONDS: u64 = 10;
-    const ONE_MINUTES_SECONDS: u64 = 60;
-    const TIMES: u64 = ONE_MINUTES_SECONDS / GATHER_INTERVAL_SECONDS;
-    let gather_duration = Duration::from_secs(GATHER_INTERVAL_SECONDS);
-    let mut ramp_up: Option<(u64, u64)> = if tpcc.ramp_up == 0 {
-        Some((0, 0))
+    terminals: &mut Terminals<'_>,
+    control: &Control,
+) -> anyhow::Result<Summary> {
+    let began = Instant::now();
+    // Counters are global, a process may benchmark more than once(such as warming up before benchmark).
+    let base = counters();
+    let (mut ramp_up, mut baking_began, first_phase) = if tpcc.ramp_up.is_zero() {
+        control.set_phase(Phase::Baking);
+        (Some(base), Some(began), tpcc.baking)
     } else {
-        None
+        control.set_phase(Phase::RampUp);
+        (None, None, tpcc.ramp_up)
     };
-    let mut ticker = interval_at(Instant::now() + gather_duration, gather_duration);
-    let mut hits = 0;
+    let mut interrupted = None;
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    let phase_end = sleep_until(began + first_phase);
+    tokio::pin!(phase_end);
+    let mut ticker = interval_at(began + tpcc.report_interval, tpcc.report_interval);
     loop {
         select! {
             _ = ticker.tick() => {
-                let mut total_new_orders = TX_NEW_ORDER.get();
-                let mut total_transactions = TX_TOTAL.get();
-                if let Some((no, tx)) = ramp_up {
-                    total_new_orders -= no;
-                    total_transactions -= tx;
-                }
-                hits += 1;
-                let tpmc_no = (total_new_orders as f64) / (hits as f64) * (TIMES as f64) ;
-                let tpmc_total = (total_transactions as f64) / (hits as f64) * (TIMES as f64);
+                let (no, tx, _) = ramp_up.unwrap_or(base);
+                let total_new_orders = TX_NEW_ORDER.get() - no;
+                let total_transactions = TX_TOTAL.get() - tx;
+                let elapsed = baking_began.unwrap_or(began).elapsed();
+                let minutes = elapsed.as_secs_f64() / 60.0;
+                let tpmc_no = per_minute(total_new_orders, minutes);
+                let tpmc_total = per_minute(total_transactions, minutes);
                 TPM_NEW_ORDER.set(tpmc_no);
                 TPM_TOTAL.set(tpmc_total);
                 info!(
-                    hits,
-                    seconds = hits * GATHER_INTERVAL_SECONDS,
+                    seconds = elapsed.as_secs(),
                     total_new_orders,
                     total_transactions,
                     baking = ramp_up.is_some(),
                     tpmC_NewOrder = tpmc_no,
                     tpmTOTAL = tpmc_total,
                 );
-                if hits / TIMES == (tpcc.ramp_up as u64) && ramp_up.is_none() {
+            }
+            _ = &mut phase_end => {
+                if ramp_up.is_none() {
                     info!("Ramp up finished");
-                    ramp_up = Some((total_new_orders, total_transactions));
-                    hits = 0;
-                } else if hits / TIMES == (tpcc.baking as u64) && ramp_up.is_some() {
-                    tx_stop.send(()).unwrap();
+                    let now = Instant::now();
+                    ramp_up = Some(counters());
+                    baking_began = Some(now);
+                    control.set_phase(Phase::Baking);
+                    phase_end.as_mut().reset(now + tpcc.baking);
+                } else {
+                    terminals.stop();
                     break;
                 }
             }
-            joined = join_set.join_next() => {
+            Some(command) = control.recv() => {
+                info!(?command, "Received control command");
+                match command {
+                    Command::Stop => {
+                        interrupted = Some("control API");
+                        terminals.stop();
+                        break;
+                    }
+                    Command::Terminals(count) => terminals.resize(count).await?,
+                    Command::Rate(tpm) => control.throttle.set_rate(tpm),
+                }
+            }
+            reason = &mut shutdown => {
+                interrupted = Some(reason);
+                terminals.stop();
+                break;
+            }
+            joined = terminals.join_set.join_next(), if !terminals.join_set.is_empty() => {
                 match joined {
                     Some(j) => {
                         j??;
@@ -225,40 +414,89 @@ async fn wait_for_benchmark(
             }
         }
     }
+    control.set_phase(Phase::Stopping);
+    if let Some(reason) = interrupted {
+        warn!(
+            reason,
+            "Benchmark interrupted, let terminals finish their current transactions"
+        );
+    }
 
-    let (total_new_orders, total_transactions) = ramp_up.unwrap();
+    // Rates are computed from measured time, which also covers interrupted benchmark.
+    let minutes = |d: Duration| d.as_secs_f64() / 60.0;
+    let (ramp_up_minutes, baking_minutes) = match baking_began {
+        Some(baking_began) => (
+            minutes(baking_began - began),
+            minutes(baking_began.elapsed()),
+        ),
+        None => (minutes(began.elapsed()), 0.0),
+    };
+    let (total_new_orders, total_transactions, total_aborted) = ramp_up.unwrap_or(counters());
     info!(
-        total_new_orders,
-        total_transactions,
-        tpmC_NewOrder = (total_new_orders as f64) / (tpcc.ramp_up as f64),
-        tpmTOTAL = (total_transactions as f64) / (tpcc.ramp_up as f64),
+        total_new_orders = total_new_orders - base.0,
+        total_transactions = total_transactions - base.1,
+        minutes = ramp_up_minutes,
+        tpmC_NewOrder = per_minute(total_new_orders - base.0, ramp_up_minutes),
+        tpmTOTAL = per_minute(total_transactions - base.1, ramp_up_minutes),
         "Result during Ramp up"
     );
     let total_new_orders = TX_NEW_ORDER.get() - total_new_orders;
     let total_transactions = TX_TOTAL.get() - total_transactions;
+    let summary = Summary {
+        new_orders: total_new_orders,
+        transactions: total_transactions,
+        minutes: baking_minutes,
+        tpmc_new_order: per_minute(total_new_orders, baking_minutes),
+        tpm_total: per_minute(total_transactions, baking_minutes),
+        aborted: TX_ABORTED.get() - total_aborted,
+        interrupted,
+    };
     info!(
-        total_new_orders,
-        total_transactions,
-        tpmC_NewOrder = (total_new_orders as f64) / (tpcc.baking as f64),
-        tpmTOTAL = (total_transactions as f64) / (tpcc.baking as f64),
+        total_new_orders = summary.new_orders,
+        total_transactions = summary.transactions,
+        minutes = summary.minutes,
+        interrupted = summary.interrupted.is_some(),
+        tpmC_NewOrder = summary.tpmc_new_order,
+        tpmTOTAL = summary.tpm_total,
+        aborted = summary.aborted,
+        abort_rate = summary.abort_rate(),
         "Result for Benchmark"
     );
-    while let Some(j) = join_set.join_next().await {
-        j??
+
+    let shutdown = shutdown_signal();
+    tokio::pin!(shutdown);
+    loop {
+        select! {
+            joined = terminals.join_set.join_next() => match joined {
+                Some(j) => j??,
+                None => break,
+            },
+            reason = &mut shutdown => {
+                warn!(reason, "Abort terminals without waiting for current transactions");
+                terminals.join_set.abort_all();
+                break;
+            }
+        }
     }
-    Ok(())
+    control.set_phase(Phase::Finished);
+    Ok(summary)
 }
 
-#[instrument(skip(sut, tpcc))]
+#[instrument(skip(sut, tpcc, control))]
 pub async fn benchmark(
     warehouses: usize,
+    scale: &ScaleModel,
     sut: Rc<Box<dyn Sut>>,
     tpcc: &TpccBenchmark,
-) -> anyhow::Result<()> {
+    control: Arc<Control>,
+) -> anyhow::Result<Summary> {
     check_weight(tpcc, warehouses)?;
-    let (tx_stop, _) = broadcast::channel::<()>(1);
-    let join_set = spawn_terminals(warehouses, sut, tpcc, &tx_stop).await?;
-    wait_for_benchmark(tpcc, join_set, tx_stop).await?;
+    if tpcc.report_interval.is_zero() {
+        return Err(anyhow!("report_interval must be greater than zero"));
+    }
+    let mut terminals = Terminals::new(warehouses, *scale, sut, tpcc, control.clone());
+    terminals.spawn(tpcc.terminals).await?;
+    let summary = wait_for_benchmark(tpcc, &mut terminals, &control).await?;
     check_weight(tpcc, warehouses)?; // report weights again
-    Ok(())
+    Ok(summary)
 }
@@ -1,4 +1,7 @@
-use rsqlbench_core::cfg::BenchConfig;
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::anyhow;
+use rsqlbench_core::cfg::{duration, BenchConfig};
 use serde::Deserialize;
 
 #[derive(Debug, Deserialize)]
@@ -8,16 +11,89 @@ pub struct Monitor {
     pub listen_addr: String,
     #[serde(default = "default_monitor_api_path")]
     pub path: String,
+
+    /// Path prefix of REST API controlling a running benchmark.
+    #[serde(default = "default_monitor_control_path")]
+    pub control_path: String,
+
+    /// Push metrics to remote receivers, useful when prometheus could not scrape us.
+    pub push: Option<Push>,
 }
 
 fn default_monitor_api_path() -> String {
     "/prometheus".to_string()
 }
 
+fn default_monitor_control_path() -> String {
+    "/control".to_string()
+}
+
 fn default_monitor_enable() -> bool {
     true
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct Push {
+    /// Push interval, such as `15s`(plain number for seconds).
+    #[serde(
+        default = "default_push_interval",
+        deserialize_with = "duration::seconds"
+    )]
+    pub interval: Duration,
+
+    /// Grouping labels attached to all pushed metrics.
+    #[serde(default)]
+    pub labels: HashMap<String, String>,
+
+    /// Prometheus Pushgateway.
+    pub pushgateway: Option<Pushgateway>,
+
+    /// OpenTelemetry OTLP/HTTP metrics receiver.
+    pub otlp: Option<Otlp>,
+}
+
+impl Push {
+    /// Interval of zero would panic when ticking.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        if self.interval.is_zero() {
+            return Err(anyhow!("push.interval must be greater than zero"));
+        }
+        Ok(())
+    }
+}
+
+fn default_push_interval() -> Duration {
+    Duration::from_secs(10)
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Pushgateway {
+    /// Base URL of pushgateway, such as `http://localhost:9091`.
+    pub url: String,
+
+    /// Job name used in grouping key.
+    #[serde(default = "default_push_job")]
+    pub job: String,
+}
+
+fn default_push_job() -> String {
+    "rsqlbench".to_string()
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Otlp {
+    /// Metrics endpoint of OTLP/HTTP receiver, such as `http://localhost:4318/v1/metrics`.
+    pub endpoint: String,
+
+    /// Extra HTTP headers, such as authorization.
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+
+    /// Value of resource attribute `service.name`.
+    #[serde(default = "default_push_job")]
+    pub service_name: String,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct RSBConfig {
     pub monitor: Option<Monitor>,
@@ -0,0 +1,222 @@
+//! Runtime control of a running benchmark, shared by benchmark loop and REST API.
+
+use std::{
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use serde::Serialize;
+use tokio::{
+    sync::mpsc,
+    time::{sleep_until, Instant},
+};
+
+use crate::monitor::{TPM_NEW_ORDER, TPM_TOTAL, TX_NEW_ORDER, TX_TOTAL};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Phase {
+    Idle,
+    Building,
+    RampUp,
+    Baking,
+    Stopping,
+    Finished,
+}
+
+#[derive(Debug)]
+pub enum Command {
+    /// Stop benchmark gracefully.
+    Stop,
+
+    /// Change count of terminals.
+    Terminals(usize),
+
+    /// Change target rate(transactions per minute), `None` for unlimited.
+    Rate(Option<f64>),
+}
+
+#[derive(Debug, Serialize)]
+pub struct Status {
+    pub phase: Phase,
+    /// Seconds elapsed since current phase began.
+    pub phase_elapsed: f64,
+    /// Seconds elapsed since benchmark began.
+    pub elapsed: f64,
+    pub terminals: usize,
+    pub target_rate: Option<f64>,
+    pub tpmc_new_order: f64,
+    pub tpm_total: f64,
+    pub tx_new_order: u64,
+    pub tx_total: u64,
+}
+
+/// Lowest rate accepted from control API, whose interval of about 2 years still fits in nanoseconds of `u64`.
+pub const MIN_RATE: f64 = 1e-6;
+
+/// Limit total transactions rate of all terminals.
+///
+/// Each transaction takes a time slot, slots are spaced evenly by target rate.
+pub struct Throttle {
+    interval_nanos: AtomicU64,
+    next_slot: Mutex<Instant>,
+}
+
+impl Default for Throttle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Throttle {
+    pub fn new() -> Self {
+        Self {
+            interval_nanos: AtomicU64::new(0),
+            next_slot: Mutex::new(Instant::now()),
+        }
+    }
+
+    pub fn set_rate(&self, tpm: Option<f64>) {
+        let interval = tpm
+            .filter(|tpm| *tpm > 0.0)
+            .map(|tpm| {
+                Duration::try_from_secs_f64(60.0 / tpm)
+                    .map(|interval| u64::try_from(interval.as_nanos()).unwrap_or(u64::MAX))
+                    .unwrap_or(u64::MAX)
+            })
+            .unwrap_or(0);
+        self.interval_nanos.store(interval, Ordering::Relaxed);
+    }
+
+    pub fn rate(&self) -> Option<f64> {
+        match self.interval_nanos.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(60.0 / Duration::from_nanos(nanos).as_secs_f64()),
+        }
+    }
+
+    pub async fn acquire(&self) {
+        let interval = self.interval_nanos.load(Ordering::Relaxed);
+        if interval == 0 {
+            return;
+        }
+        let slot = {
+            let mut next_slot = self.next_slot.lock().unwrap();
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + Duration::from_nanos(interval);
+            slot
+        };
+        sleep_until(slot).await;
+    }
+}
+
+pub struct Control {
+    phase: Mutex<(Phase, Instant)>,
+    started: Mutex<Option<Instant>>,
+    terminals: AtomicUsize,
+    pub throttle: Throttle,
+    tx_command: mpsc::UnboundedSender<Command>,
+    rx_command: tokio::sync::Mutex<mpsc::UnboundedReceiver<Command>>,
+}
+
+impl Default for Control {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Control {
+    pub fn new() -> Self {
+        let (tx_command, rx_command) = mpsc::unbounded_channel();
+        Self {
+            phase: Mutex::new((Phase::Idle, Instant::now())),
+            started: Mutex::new(None),
+            terminals: AtomicUsize::new(0),
+            throttle: Throttle::new(),
+            tx_command,
+            rx_command: tokio::sync::Mutex::new(rx_command),
+        }
+    }
+
+    pub fn set_phase(&self, phase: Phase) {
+        let now = Instant::now();
+        *self.phase.lock().unwrap() = (phase, now);
+        if phase == Phase::RampUp || phase == Phase::Baking {
+            self.started.lock().unwrap().get_or_insert(now);
+        }
+    }
+
+    pub fn phase(&self) -> Phase {
+        self.phase.lock().unwrap().0
+    }
+
+    pub fn set_terminals(&self, terminals: usize) {
+        self.terminals.store(terminals, Ordering::Relaxed);
+    }
+
+    pub fn send(&self, command: Command) {
+        // Receiver lives as long as control itself.
+        self.tx_command.send(command).unwrap();
+    }
+
+    pub async fn recv(&self) -> Option<Command> {
+        self.rx_command.lock().await.recv().await
+    }
+
+    pub fn status(&self) -> Status {
+        let (phase, phase_began) = *self.phase.lock().unwrap();
+        Status {
+            phase,
+            phase_elapsed: phase_began.elapsed().as_secs_f64(),
+            elapsed: self
+                .started
+                .lock()
+                .unwrap()
+                .map(|x| x.elapsed().as_secs_f64())
+                .unwrap_or_default(),
+            terminals: self.terminals.load(Ordering::Relaxed),
+            target_rate: self.throttle.rate(),
+            tpmc_new_order: TPM_NEW_ORDER.get(),
+            tpm_total: TPM_TOTAL.get(),
+            tx_new_order: TX_NEW_ORDER.get(),
+            tx_total: TX_TOTAL.get(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use tokio::time::Instant;
+
+    use super::Throttle;
+
+    #[tokio::test(start_paused = true)]
+    async fn throttle() {
+        let throttle = Throttle::new();
+        assert_eq!(throttle.rate(), None);
+
+        throttle.set_rate(Some(600.0)); // 10 per second
+        assert_eq!(throttle.rate(), Some(600.0));
+        let begin = Instant::now();
+        for _ in 0..10 {
+            throttle.acquire().await;
+        }
+        assert!(begin.elapsed() >= Duration::from_millis(900));
+
+        // Interval overflowing nanoseconds is capped instead of panicking.
+        throttle.set_rate(Some(1e-300));
+        assert!(throttle.rate().is_some_and(|tpm| tpm > 0.0));
+
+        throttle.set_rate(None);
+        let begin = Instant::now();
+        for _ in 0..10 {
+            throttle.acquire().await;
+        }
+        assert!(begin.elapsed() < Duration::from_millis(1));
+    }
+}
@@ -1,12 +1,18 @@
+use anyhow::Context;
 use rsqlbench_core::{
     cfg,
     tpcc::{
+        csv::{self, CsvWriter},
         loader::Loader,
         model::{ItemGenerator, Warehouse, WarehouseGenerator},
         sut::Sut,
     },
 };
-use std::rc::Rc;
+use std::{
+    path::{Path, PathBuf},
+    rc::Rc,
+    sync::Mutex,
+};
 use tokio::task::JoinSet;
 use tracing::{info, instrument};
 
@@ -20,14 +26,16 @@ async fn load_warehouse(
     loader.load_warehouses(rx).await
 }
 
-#[instrument(skip(sut))]
-pub async fn load_all_items(sut: Rc<Box<dyn Sut>>, _: &cfg::Loader) -> anyhow::Result<()> {
+#[instrument(skip(sut, loader_cfg))]
+pub async fn load_all_items(sut: Rc<Box<dyn Sut>>, loader_cfg: &cfg::Loader) -> anyhow::Result<()> {
+    const ITEMS_PER_LOAD: u32 = 50000;
     info!("Loading items...");
     let mut loader = sut.loader().await?;
-    loader.load_items(ItemGenerator::new(1..=50000)).await?;
-    loader
-        .load_items(ItemGenerator::new(50001..=100000))
-        .await?;
+    let items = loader_cfg.scale.items as u32;
+    for start in (1..=items).step_by(ITEMS_PER_LOAD as _) {
+        let end = (start + ITEMS_PER_LOAD - 1).min(items);
+        loader.load_items(ItemGenerator::new(start..=end)).await?;
+    }
     info!("Items loaded.");
     Ok(())
 }
@@ -60,3 +68,99 @@ pub async fn load_all_warehouses(
     info!("Warehouses loaded.");
     Ok(())
 }
+
+/// Generate CSV files of all tables, warehouses are generated in parallel if split.
+pub fn generate_csv(
+    loader_cfg: &cfg::Loader,
+    out: &Path,
+    per_warehouse: bool,
+) -> anyhow::Result<()> {
+    std::fs::create_dir_all(out).with_context(|| format!("Could not create {out:?}"))?;
+    let scale = &loader_cfg.scale;
+    let mut writer = CsvWriter::new(out, None);
+    csv::write_items(&mut writer, scale)?;
+    if !per_warehouse {
+        for warehouse in WarehouseGenerator::new(1..=loader_cfg.warehouse) {
+            info!("Generating warehouse ID={id}", id = warehouse.id);
+            csv::write_warehouse(&mut writer, &warehouse, scale)?;
+        }
+        writer.finish()?;
+        return Ok(());
+    }
+    writer.finish()?;
+
+    let warehouses = Mutex::new(WarehouseGenerator::new(1..=loader_cfg.warehouse));
+    std::thread::scope(|s| {
+        let workers = (0..loader_cfg.monkeys.max(1))
+            .map(|_| {
+                s.spawn(|| -> anyhow::Result<()> {
+                    loop {
+                        let Some(warehouse) = warehouses.lock().unwrap().next() else {
+                            return Ok(());
+                        };
+                        info!("Generating warehouse ID={id}", id = warehouse.id);
+                        let mut writer = CsvWriter::new(out, Some(warehouse.id));
+                        csv::write_warehouse(&mut writer, &warehouse, scale)?;
+                        writer.finish()?;
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        workers
+            .into_iter()
+            .try_for_each(|worker| worker.join().expect("CSV generator panicked"))
+    })
+}
+
+#[instrument(skip(loader, rx))]
+async fn load_csv_files(
+    loader_id: usize,
+    loader: Box<dyn Loader>,
+    rx: async_channel::Receiver<(&'static str, PathBuf)>,
+) -> anyhow::Result<()> {
+    let mut loader = loader;
+    while let Ok((table, path)) = rx.recv().await {
+        info!(table, ?path, "Loading CSV file");
+        loader.load_csv(table, &path).await?;
+    }
+    Ok(())
+}
+
+/// Load all CSV files in directory generated by `tpcc generate`.
+#[instrument(skip(sut, loader_cfg))]
+pub async fn load_all_csv(
+    sut: Rc<Box<dyn Sut>>,
+    loader_cfg: &cfg::Loader,
+    dir: &Path,
+) -> anyhow::Result<()> {
+    let mut files = vec![];
+    for entry in std::fs::read_dir(dir).with_context(|| format!("Could not read {dir:?}"))? {
+        let path = entry?.path();
+        if let Some(table) = csv::table_of(&path) {
+            files.push((table, path));
+        }
+    }
+    if files.is_empty() {
+        return Err(anyhow::anyhow!("No CSV file found in {dir:?}"));
+    }
+    // Large files first for better parallelism.
+    files.sort_by_key(|(_, path)| {
+        std::cmp::Reverse(path.metadata().map(|m| m.len()).unwrap_or_default())
+    });
+
+    let mut join_set = JoinSet::new();
+    let (tx, rx) = async_channel::unbounded();
+    for loader_id in 0..loader_cfg.monkeys.clamp(1, files.len()) {
+        let loader = sut.loader().await?;
+        join_set.spawn(load_csv_files(loader_id, loader, rx.clone()));
+    }
+    for file in files {
+        tx.send(file).await?;
+    }
+    tx.close();
+    while let Some(j) = join_set.join_next().await {
+        j??
+    }
+    info!("CSV files loaded.");
+    Ok(())
+}
@@ -1,23 +1,27 @@
 mod benchmark;
 mod cfg;
+mod control;
 mod loader;
 mod monitor;
+mod pipeline;
+mod validate;
 
-use std::rc::Rc;
+use std::{path::PathBuf, rc::Rc, sync::Arc, time::Duration};
 
 use anyhow::{anyhow, Context};
 use cfg::RSBConfig;
 use clap::{Parser, Subcommand};
 use config::{Config, Environment, File};
-use monitor::{register_registry, spawn_prometheus, REGISTRY};
+use control::{Control, Phase};
+use monitor::{register_registry, spawn_rest_api, Pusher, REGISTRY};
 use rsqlbench_core::{
-    cfg::Connection,
+    cfg::{tpcc::IsolationLevel, Connection},
     tpcc::sut::{MysqlSut, Sut},
 };
 #[cfg(feature = "yasdb")]
 use rsqlbench_yasdb::YasdbSut;
 use time::{format_description::well_known::Rfc3339, UtcOffset};
-use tracing::{error, info, level_filters::LevelFilter, warn};
+use tracing::{info, level_filters::LevelFilter, warn};
 use tracing_subscriber::{fmt::time::OffsetTime, EnvFilter};
 use url::Url;
 
@@ -36,18 +40,82 @@ struct Cli {
 enum Command {
     #[command(subcommand)]
     Tpcc(TpccCommand),
+
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Debug, Subcommand)]
+enum ConfigCommand {
+    /// Check config, transaction weights and connectivity of every connection string.
+    Validate,
+
+    /// Print effective config merged from file and `RSB_` environment variables, secrets are masked.
+    Show,
 }
 
 #[derive(Debug, Subcommand)]
 enum TpccCommand {
     /// Build schema and load data for TPC-C benchmark.
-    Build,
+    Build {
+        /// Load CSV files generated by `tpcc generate` instead of generating data.
+        #[arg(long)]
+        from: Option<PathBuf>,
+
+        /// Skip building if schema exists.
+        #[arg(long, conflicts_with = "force")]
+        if_not_exists: bool,
+
+        /// Destroy schema first if it exists.
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Generate data for TPC-C benchmark as CSV files.
+    Generate {
+        /// Directory for CSV files.
+        #[arg(long)]
+        out: PathBuf,
+
+        /// Split files per warehouse so that they could be generated and loaded in parallel.
+        #[arg(long)]
+        per_warehouse: bool,
+    },
 
     /// Benchmark TPC-C.
     Benchmark,
 
     /// Destroy schema.
     Destroy,
+
+    /// Check consistency conditions of TPC-C.
+    Check,
+
+    /// Destroy(if exists), build, verify loaded data, warm up, benchmark, check consistency and report.
+    Run {
+        /// Load CSV files generated by `tpcc generate` instead of generating data.
+        #[arg(long)]
+        from: Option<PathBuf>,
+
+        /// Benchmark before the measured one without reporting, such as `5m`.
+        #[arg(long, value_parser = humantime::parse_duration)]
+        warm_up: Option<Duration>,
+    },
+
+    /// Benchmark at every isolation level in turn, report throughput, abort rate and consistency of each level.
+    Sweep {
+        /// Isolation levels to benchmark, such as `read_committed,serializable`.
+        #[arg(
+            long,
+            value_delimiter = ',',
+            default_value = "read_uncommitted,read_committed,repeatable_read,serializable"
+        )]
+        isolation: Vec<IsolationLevel>,
+
+        /// Destroy and build schema before every level so that all levels begin with the same data.
+        #[arg(long)]
+        rebuild: bool,
+    },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -57,6 +125,9 @@ enum DbVerifyError {
 
     #[error("Used different SUT or RDBMS in connection string list")]
     DifferentSut,
+
+    #[error("Could not determine SUT from connection strings, `connection.sut` must be specified")]
+    UnknownSut,
 }
 
 fn determine_db_type(connection: &Connection) -> Result<String, DbVerifyError> {
@@ -80,7 +151,7 @@ fn determine_db_type(connection: &Connection) -> Result<String, DbVerifyError> {
     } else if parsed.iter().all(Result::is_err) {
         warn!("Could not determine db type according to db connection string");
 
-        let sut = connection.sut.clone().expect("SUT must be specified");
+        let sut = connection.sut.clone().ok_or(DbVerifyError::UnknownSut)?;
         warn!("Fallback SUT/db type to {}", sut);
         Ok(sut)
     } else {
@@ -116,34 +187,67 @@ async fn main() -> anyhow::Result<()> {
         .with_thread_names(true)
         .init();
 
-    let root_cfg: RSBConfig = Config::builder()
+    let config = Config::builder()
         .add_source(File::with_name(&cli.config))
         .add_source(Environment::with_prefix("RSB"))
         .build()
-        .with_context(|| "Could not load config properly.")?
+        .with_context(|| "Could not load config properly.")?;
+    if let Command::Config(ConfigCommand::Show) = cli.command {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&validate::masked(&config)?)?
+        );
+        return Ok(());
+    }
+    info!(
+        config = %serde_json::to_string(&validate::masked(&config)?)?,
+        "Using config"
+    );
+    let root_cfg: RSBConfig = config
         .try_deserialize()
         .with_context(|| "Could not deserialize config file.")?;
-    info!(?root_cfg, "Using config");
 
     let cfg = root_cfg.bench;
     let sut_type = determine_db_type(&cfg.connection)?;
 
     info!(sut_type);
     let sut: Rc<Box<dyn Sut>> = match sut_type.as_str() {
-        "mysql" => Rc::new(Box::new(MysqlSut::new(cfg.connection, cfg.loader.warehouse))),
+        "mysql" => Rc::new(Box::new(MysqlSut::new(
+            cfg.connection.clone(),
+            cfg.loader.warehouse,
+            cfg.loader.scale,
+            cfg.loader.method,
+            cfg.loader.after_loaded,
+            cfg.schema.clone(),
+        ))),
         #[cfg(feature = "yasdb")]
-        "yasdb" => Rc::new(Box::new(YasdbSut::new(cfg.connection, cfg.loader.warehouse))),
+        "yasdb" => Rc::new(Box::new(YasdbSut::new(cfg.connection.clone(), cfg.loader.warehouse, cfg.loader.scale, cfg.loader.method, cfg.loader.batch_size, cfg.schema.clone()))),
         #[cfg(not(feature = "yasdb"))]
         "yasdb" => return Err(anyhow!("yasdb not implement in current rsqlbench distribution, please compile rsqlbench with feature `yasdb`.")),
         _ => return Err(anyhow!("Unsupported sut/db.")),
     };
+    if let Command::Config(ConfigCommand::Validate) = cli.command {
+        return validate::validate(sut.as_ref().as_ref(), &cfg, root_cfg.monitor.as_ref()).await;
+    }
 
     register_registry()?;
 
-    let prometheus = if let Some(monitor) = root_cfg.monitor {
+    let pusher = root_cfg
+        .monitor
+        .as_ref()
+        .and_then(|m| m.push.clone())
+        .map(|push| push.verify().map(|_| Arc::new(Pusher::new(push))))
+        .transpose()?;
+    let pushing = pusher.clone().map(|pusher| {
+        info!("Enable pushing metrics");
+        tokio::spawn(async move { pusher.run().await })
+    });
+
+    let control = Arc::new(Control::new());
+    let rest_api = if let Some(monitor) = root_cfg.monitor {
         if monitor.enable {
-            info!("Enable REST API for prometheus");
-            Some(tokio::spawn(spawn_prometheus(monitor)))
+            info!("Enable REST API for prometheus and controlling");
+            Some(tokio::spawn(spawn_rest_api(monitor, control.clone())))
         } else {
             None
         }
@@ -152,27 +256,64 @@ async fn main() -> anyhow::Result<()> {
     };
 
     match cli.command {
+        Command::Config(_) => unreachable!("config commands are handled before"),
         Command::Tpcc(tpcc_cmd) => match tpcc_cmd {
-            TpccCommand::Build => {
-                info!("Building schema...");
-                sut.build_schema().await?;
-                info!("Loading all items...");
-                loader::load_all_items(sut.clone(), &cfg.loader).await?;
-                info!("Loading all warehouses...");
-                loader::load_all_warehouses(sut.clone(), &cfg.loader).await?;
-                info!("Data loaded.");
-                info!("Do some operations after data loading (such as building foreign keys and constraints)...");
-                sut.after_loaded().await?;
+            TpccCommand::Build {
+                from,
+                if_not_exists,
+                force,
+            } => {
+                let exists = (if_not_exists || force) && sut.schema_exists().await?;
+                if exists && if_not_exists {
+                    info!("Schema exists, building skipped.");
+                } else {
+                    if exists {
+                        info!("Destroying existing schema...");
+                        sut.destroy_schema().await?;
+                    }
+                    pipeline::build(sut.clone(), &cfg, from.as_deref(), &control).await?;
+                }
             }
             TpccCommand::Benchmark => {
                 info!("Prepare to benchmark...");
-                benchmark::benchmark(cfg.loader.warehouse as _, sut.clone(), &cfg.benchmark.tpcc)
-                    .await?;
+                let summary = benchmark::benchmark(
+                    cfg.loader.warehouse as _,
+                    &cfg.loader.scale,
+                    sut.clone(),
+                    &cfg.benchmark.tpcc,
+                    control.clone(),
+                )
+                .await?;
                 let encoder = prometheus::TextEncoder::new();
                 let gathered = encoder.encode_to_string(&REGISTRY.gather())?;
-                info!("Benchmark finished.");
+                match summary.interrupted {
+                    Some(reason) => warn!(reason, "Benchmark interrupted."),
+                    None => info!("Benchmark finished."),
+                }
                 info!("{gathered}");
             }
+            TpccCommand::Generate { out, per_warehouse } => {
+                info!(?out, per_warehouse, "Generating CSV files...");
+                let loader_cfg = cfg.loader;
+                tokio::task::spawn_blocking(move || {
+                    loader::generate_csv(&loader_cfg, &out, per_warehouse)
+                })
+                .await??;
+                info!("CSV files generated.");
+            }
+            TpccCommand::Check => {
+                info!("Checking consistency...");
+                sut.check_consistency().await?;
+                info!("Consistency checked.");
+            }
+            TpccCommand::Run { from, warm_up } => {
+                pipeline::run(sut.clone(), &cfg, from.as_deref(), warm_up, control.clone()).await?;
+                let encoder = prometheus::TextEncoder::new();
+                info!("{}", encoder.encode_to_string(&REGISTRY.gather())?);
+            }
+            TpccCommand::Sweep { isolation, rebuild } => {
+                pipeline::sweep(sut.clone(), &cfg, &isolation, rebuild, control.clone()).await?;
+            }
             TpccCommand::Destroy => {
                 info!("Destroying schema...");
                 sut.destroy_schema().await?;
@@ -181,7 +322,15 @@ async fn main() -> anyhow::Result<()> {
         },
     }
 
-    if let Some(p) = prometheus {
+    if let Some(p) = pushing {
+        p.abort();
+    }
+    if let Some(pusher) = pusher {
+        // Push final metrics.
+        pusher.push().await;
+    }
+    control.set_phase(Phase::Finished);
+    if let Some(p) = rest_api {
         p.abort();
     }
 
@@ -1,8 +1,31 @@
-use axum::{routing::get, Router};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anyhow::anyhow;
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post, put},
+    Json, Router,
+};
 use once_cell::sync::Lazy;
-use prometheus::{Gauge, IntCounter, Registry};
+use prometheus::{
+    proto::{MetricFamily, MetricType},
+    Encoder, Gauge, IntCounter, Registry,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::time::interval;
+use tracing::{debug, warn};
+use url::Url;
 
-use crate::cfg::Monitor;
+use crate::{
+    cfg::{Monitor, Otlp, Push, Pushgateway},
+    control::{Command, Control, Phase, Status, MIN_RATE},
+};
 
 pub static REGISTRY: Lazy<Registry> = Lazy::new(Registry::new);
 pub static TX_NEW_ORDER: Lazy<IntCounter> = Lazy::new(|| {
@@ -12,6 +35,10 @@ pub static TPM_NEW_ORDER: Lazy<Gauge> =
     Lazy::new(|| Gauge::new("tpmc_new_order", "tpmC(New Order)").expect("metric can be created"));
 pub static TX_TOTAL: Lazy<IntCounter> =
     Lazy::new(|| IntCounter::new("tx_total", "Transaction TOTAL").expect("metric can be created"));
+pub static TX_ABORTED: Lazy<IntCounter> = Lazy::new(|| {
+    IntCounter::new("tx_aborted", "Transactions aborted by conflicts")
+        .expect("metric can be created")
+});
 pub static TPM_TOTAL: Lazy<Gauge> =
     Lazy::new(|| Gauge::new("tpmc_total", "tpmC TOTAL").expect("metric can be created"));
 
@@ -20,6 +47,7 @@ pub fn register_registry() -> anyhow::Result<()> {
     REGISTRY.register(Box::new(TX_NEW_ORDER.clone()))?;
     REGISTRY.register(Box::new(TPM_TOTAL.clone()))?;
     REGISTRY.register(Box::new(TX_TOTAL.clone()))?;
+    REGISTRY.register(Box::new(TX_ABORTED.clone()))?;
     Ok(())
 }
 
@@ -30,10 +58,441 @@ async fn prometheus_metrics() -> String {
         .expect("Failed to encode metrics for prometheus")
 }
 
-pub async fn spawn_prometheus(cfg: Monitor) -> Result<(), std::io::Error> {
-    let app = Router::new().route(&cfg.path, get(prometheus_metrics));
+async fn status(State(control): State<Arc<Control>>) -> Json<Status> {
+    Json(control.status())
+}
+
+fn accept_command(control: &Control, command: Command) -> Result<Json<Status>, StatusCode> {
+    if matches!(control.phase(), Phase::RampUp | Phase::Baking) {
+        control.send(command);
+        Ok(Json(control.status()))
+    } else {
+        Err(StatusCode::CONFLICT)
+    }
+}
+
+async fn stop(State(control): State<Arc<Control>>) -> Result<Json<Status>, StatusCode> {
+    accept_command(&control, Command::Stop)
+}
+
+#[derive(Debug, Deserialize)]
+struct TerminalsRequest {
+    terminals: usize,
+}
+
+async fn terminals(
+    State(control): State<Arc<Control>>,
+    Json(request): Json<TerminalsRequest>,
+) -> Result<Json<Status>, StatusCode> {
+    if request.terminals == 0 {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    accept_command(&control, Command::Terminals(request.terminals))
+}
+
+#[derive(Debug, Deserialize)]
+struct RateRequest {
+    /// Transactions per minute, `null` for unlimited, at least [`MIN_RATE`].
+    tpm: Option<f64>,
+}
+
+async fn rate(
+    State(control): State<Arc<Control>>,
+    Json(request): Json<RateRequest>,
+) -> Result<Json<Status>, StatusCode> {
+    if request.tpm.is_some_and(|tpm| tpm < MIN_RATE) {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+    accept_command(&control, Command::Rate(request.tpm))
+}
+
+fn router(cfg: &Monitor, control: Arc<Control>) -> Router {
+    let api = Router::new()
+        .route("/status", get(status))
+        .route("/stop", post(stop))
+        .route("/terminals", put(terminals))
+        .route("/rate", put(rate))
+        .with_state(control);
+    Router::new()
+        .route(&cfg.path, get(prometheus_metrics))
+        .nest(&cfg.control_path, api)
+}
+
+pub async fn spawn_rest_api(cfg: Monitor, control: Arc<Control>) -> Result<(), std::io::Error> {
+    let app = router(&cfg, control);
     let listener = tokio::net::TcpListener::bind(cfg.listen_addr.clone())
         .await
         .unwrap();
     axum::serve(listener, app).await
 }
+
+/// Push metrics periodically to pushgateway and/or OTLP receiver.
+pub struct Pusher {
+    cfg: Push,
+    client: reqwest::Client,
+    start_time: SystemTime,
+}
+
+impl Pusher {
+    pub fn new(cfg: Push) -> Self {
+        Self {
+            cfg,
+            client: reqwest::Client::new(),
+            start_time: SystemTime::now(),
+        }
+    }
+
+    pub async fn run(&self) {
+        let mut ticker = interval(self.cfg.interval);
+        loop {
+            ticker.tick().await;
+            self.push().await;
+        }
+    }
+
+    /// Push all metrics once, failures are only reported since monitoring should not break benchmark.
+    pub async fn push(&self) {
+        let families = REGISTRY.gather();
+        if let Some(pushgateway) = &self.cfg.pushgateway {
+            match push_to_gateway(&self.client, pushgateway, &self.cfg.labels, &families).await {
+                Ok(_) => debug!("Metrics pushed to pushgateway"),
+                Err(e) => warn!(?e, "Failed to push metrics to pushgateway"),
+            }
+        }
+        if let Some(otlp) = &self.cfg.otlp {
+            match push_to_otlp(
+                &self.client,
+                otlp,
+                &self.cfg.labels,
+                &families,
+                self.start_time,
+            )
+            .await
+            {
+                Ok(_) => debug!("Metrics pushed to OTLP receiver"),
+                Err(e) => warn!(?e, "Failed to push metrics to OTLP receiver"),
+            }
+        }
+    }
+}
+
+/// Grouping key is appended to URL as `/metrics/job/<job>/<label>/<value>...`.
+fn pushgateway_url(cfg: &Pushgateway, labels: &HashMap<String, String>) -> anyhow::Result<Url> {
+    let mut url = Url::parse(&cfg.url)?;
+    {
+        let mut segments = url
+            .path_segments_mut()
+            .map_err(|_| anyhow!("Invalid pushgateway url: {}", cfg.url))?;
+        segments.pop_if_empty().extend(["metrics", "job", &cfg.job]);
+        for (name, value) in labels.iter().collect::<BTreeMap<_, _>>() {
+            segments.extend([name, value]);
+        }
+    }
+    Ok(url)
+}
+
+async fn push_to_gateway(
+    client: &reqwest::Client,
+    cfg: &Pushgateway,
+    labels: &HashMap<String, String>,
+    families: &[MetricFamily],
+) -> anyhow::Result<()> {
+    let encoder = prometheus::TextEncoder::new();
+    let body = encoder.encode_to_string(families)?;
+    client
+        .put(pushgateway_url(cfg, labels)?)
+        .header(reqwest::header::CONTENT_TYPE, encoder.format_type())
+        .body(body)
+        .send()
+        .await?
+        .error_for_status()?;
+    Ok(())
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+fn otlp_attributes<'a>(labels: impl IntoIterator<Item = (&'a str, &'a str)>) -> Value {
+    labels
+        .into_iter()
+        .map(|(key, value)| json!({ "key": key, "value": { "stringValue": value } }))
+        .collect()
+}
+
+/// Encode metrics as OTLP JSON (ExportMetricsServiceRequest).
+///
+/// Counters are mapped to cumulative monotonic sums and gauges to gauges, others are skipped.
+fn otlp_payload(
+    cfg: &Otlp,
+    labels: &HashMap<String, String>,
+    families: &[MetricFamily],
+    start_time: SystemTime,
+    now: SystemTime,
+) -> Value {
+    let start_time = unix_nanos(start_time);
+    let now = unix_nanos(now);
+    let metrics = families
+        .iter()
+        .filter_map(|family| {
+            let data_points = family.get_metric().iter().map(|m| {
+                let attributes =
+                    otlp_attributes(m.get_label().iter().map(|l| (l.get_name(), l.get_value())));
+                (attributes, m)
+            });
+            let (kind, data) = match family.get_field_type() {
+                MetricType::COUNTER => (
+                    "sum",
+                    json!({
+                        "aggregationTemporality": 2, // AGGREGATION_TEMPORALITY_CUMULATIVE
+                        "isMonotonic": true,
+                        "dataPoints": data_points.map(|(attributes, m)| json!({
+                            "attributes": attributes,
+                            "startTimeUnixNano": start_time,
+                            "timeUnixNano": now,
+                            "asDouble": m.get_counter().get_value(),
+                        })).collect::<Vec<_>>(),
+                    }),
+                ),
+                MetricType::GAUGE => (
+                    "gauge",
+                    json!({
+                        "dataPoints": data_points.map(|(attributes, m)| json!({
+                            "attributes": attributes,
+                            "timeUnixNano": now,
+                            "asDouble": m.get_gauge().get_value(),
+                        })).collect::<Vec<_>>(),
+                    }),
+                ),
+                other => {
+                    debug!(
+                        name = family.get_name(),
+                        ?other,
+                        "Unsupported metric type for OTLP"
+                    );
+                    return None;
+                }
+            };
+            Some(json!({
+                "name": family.get_name(),
+                "description": family.get_help(),
+                kind: data,
+            }))
+        })
+        .collect::<Vec<_>>();
+    let resource_attributes = otlp_attributes(
+        [("service.name", cfg.service_name.as_str())]
+            .into_iter()
+            .chain(
+                labels
+                    .iter()
+                    .collect::<BTreeMap<_, _>>()
+                    .into_iter()
+                    .map(|(k, v)| (k.as_str(), v.as_str())),
+            ),
+    );
+    json!({
+        "resourceMetrics": [{
+            "resource": { "attributes": resource_attributes },
+            "scopeMetrics": [{
+                "scope": { "name": "rsqlbench", "version": env!("CARGO_PKG_VERSION") },
+                "metrics": metrics,
+            }],
+        }],
+    })
+}
+
+async fn push_to_otlp(
+    client: &reqwest::Client,
+    cfg: &Otlp,
+    labels: &HashMap<String, String>,
+    families: &[MetricFamily],
+    start_time: SystemTime,
+) -> anyhow::Result<()> {
+    let payload = otlp_payload(cfg, labels, families, start_time, SystemTime::now());
+    let mut request = client.post(&cfg.endpoint).json(&payload);
+    for (name, value) in &cfg.headers {
+        request = request.header(name, value);
+    }
+    request.send().await?.error_for_status()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use axum::{body::Bytes, http::Method, http::Uri, Router};
+    use prometheus::{Gauge, IntCounter, Registry};
+    use serde_json::Value;
+    use tokio::sync::mpsc;
+
+    use std::sync::Arc;
+
+    use crate::{
+        cfg::{Monitor, Otlp, Pushgateway},
+        control::{Command, Control, Phase},
+    };
+
+    use super::{push_to_gateway, push_to_otlp, router};
+
+    /// Stand-in receiver which records every request.
+    async fn receiver() -> (String, mpsc::UnboundedReceiver<(Method, Uri, Bytes)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = Router::new().fallback(move |method: Method, uri: Uri, body: Bytes| {
+            let tx = tx.clone();
+            async move {
+                tx.send((method, uri, body)).unwrap();
+            }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (format!("http://{addr}"), rx)
+    }
+
+    fn registry() -> Registry {
+        let registry = Registry::new();
+        let counter = IntCounter::new("tx_total", "Transaction TOTAL").unwrap();
+        let gauge = Gauge::new("tpmc_total", "tpmC TOTAL").unwrap();
+        counter.inc_by(3);
+        gauge.set(1.5);
+        registry.register(Box::new(counter)).unwrap();
+        registry.register(Box::new(gauge)).unwrap();
+        registry
+    }
+
+    fn labels() -> HashMap<String, String> {
+        HashMap::from([("instance".to_string(), "bench-1".to_string())])
+    }
+
+    #[tokio::test]
+    async fn pushgateway() {
+        let (addr, mut rx) = receiver().await;
+        let cfg = Pushgateway {
+            url: addr,
+            job: "rsqlbench".to_string(),
+        };
+        push_to_gateway(
+            &reqwest::Client::new(),
+            &cfg,
+            &labels(),
+            &registry().gather(),
+        )
+        .await
+        .unwrap();
+
+        let (method, uri, body) = rx.recv().await.unwrap();
+        let body = String::from_utf8(body.to_vec()).unwrap();
+        assert_eq!(method, Method::PUT);
+        assert_eq!(uri.path(), "/metrics/job/rsqlbench/instance/bench-1");
+        assert!(body.contains("tx_total 3"), "{body}");
+        assert!(body.contains("tpmc_total 1.5"), "{body}");
+    }
+
+    #[tokio::test]
+    async fn otlp() {
+        let (addr, mut rx) = receiver().await;
+        let cfg = Otlp {
+            endpoint: format!("{addr}/v1/metrics"),
+            headers: HashMap::new(),
+            service_name: "rsqlbench".to_string(),
+        };
+        push_to_otlp(
+            &reqwest::Client::new(),
+            &cfg,
+            &labels(),
+            &registry().gather(),
+            std::time::SystemTime::now(),
+        )
+        .await
+        .unwrap();
+
+        let (method, uri, body) = rx.recv().await.unwrap();
+        assert_eq!(method, Method::POST);
+        assert_eq!(uri.path(), "/v1/metrics");
+        let payload: Value = serde_json::from_slice(&body).unwrap();
+        let resource = &payload["resourceMetrics"][0];
+        assert_eq!(
+            resource["resource"]["attributes"][1]["key"].as_str(),
+            Some("instance")
+        );
+        let metrics = resource["scopeMetrics"][0]["metrics"].as_array().unwrap();
+        let find = |name: &str| metrics.iter().find(|m| m["name"] == name).unwrap();
+        assert_eq!(
+            find("tx_total")["sum"]["dataPoints"][0]["asDouble"].as_f64(),
+            Some(3.0)
+        );
+        assert_eq!(find("tx_total")["sum"]["isMonotonic"].as_bool(), Some(true));
+        assert_eq!(
+            find("tpmc_total")["gauge"]["dataPoints"][0]["asDouble"].as_f64(),
+            Some(1.5)
+        );
+    }
+
+    #[tokio::test]
+    async fn control() {
+        let cfg = Monitor {
+            enable: true,
+            listen_addr: "127.0.0.1:0".to_string(),
+            path: "/prometheus".to_string(),
+            control_path: "/control".to_string(),
+            push: None,
+        };
+        let control = Arc::new(Control::new());
+        let listener = tokio::net::TcpListener::bind(&cfg.listen_addr)
+            .await
+            .unwrap();
+        let addr = format!("http://{}/control", listener.local_addr().unwrap());
+        let app = router(&cfg, control.clone());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        let client = reqwest::Client::new();
+
+        let status: Value = client
+            .get(format!("{addr}/status"))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(status["phase"], "idle");
+
+        // Nothing to control before benchmarking.
+        let response = client.post(format!("{addr}/stop")).send().await.unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::CONFLICT);
+
+        control.set_phase(Phase::Baking);
+        let response = client
+            .put(format!("{addr}/terminals"))
+            .json(&serde_json::json!({ "terminals": 5 }))
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+        assert!(matches!(control.recv().await, Some(Command::Terminals(5))));
+
+        let response = client
+            .put(format!("{addr}/rate"))
+            .json(&serde_json::json!({ "tpm": -1.0 }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+        let response = client
+            .put(format!("{addr}/rate"))
+            .json(&serde_json::json!({ "tpm": 1e-300 }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+        let response = client.post(format!("{addr}/stop")).send().await.unwrap();
+        assert!(response.status().is_success());
+        assert!(matches!(control.recv().await, Some(Command::Stop)));
+    }
+}
@@ -0,0 +1,315 @@
+use std::{
+    future::Future,
+    path::Path,
+    rc::Rc,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::anyhow;
+use rsqlbench_core::{
+    cfg::{tpcc::IsolationLevel, BenchConfig},
+    tpcc::{check::CheckError, sut::Sut},
+};
+use tracing::{error, info, warn};
+
+use crate::{
+    benchmark::{self, Summary},
+    control::{Control, Phase},
+    loader,
+};
+
+/// Build schema, load data and do operations after loaded.
+pub async fn build(
+    sut: Rc<Box<dyn Sut>>,
+    cfg: &BenchConfig,
+    from: Option<&Path>,
+    control: &Control,
+) -> anyhow::Result<()> {
+    control.set_phase(Phase::Building);
+    info!("Building schema...");
+    let began = Instant::now();
+    sut.build_schema().await?;
+    info!(elapsed = ?began.elapsed(), "Schema built.");
+    let began = Instant::now();
+    if let Some(dir) = from {
+        info!("Loading CSV files...");
+        loader::load_all_csv(sut.clone(), &cfg.loader, dir).await?;
+    } else {
+        info!("Loading all items...");
+        loader::load_all_items(sut.clone(), &cfg.loader).await?;
+        info!("Loading all warehouses...");
+        loader::load_all_warehouses(sut.clone(), &cfg.loader).await?;
+    }
+    info!(elapsed = ?began.elapsed(), "Data loaded.");
+    info!(
+        "Do some operations after data loading (such as building foreign keys and constraints)..."
+    );
+    let began = Instant::now();
+    sut.after_loaded().await?;
+    info!(elapsed = ?began.elapsed(), "Operations after data loading done.");
+    Ok(())
+}
+
+enum StageResult {
+    Done,
+    Skipped(String),
+    Failed(String),
+    /// Benchmark finished early, such as by Ctrl-C or control API.
+    Interrupted(&'static str),
+}
+
+#[derive(Default)]
+struct Stages(Vec<(&'static str, Duration, StageResult)>);
+
+impl Stages {
+    /// Run a stage, unsupported checks are skipped instead of failed. Output is returned only if done.
+    async fn run<T>(
+        &mut self,
+        name: &'static str,
+        stage: impl Future<Output = anyhow::Result<T>>,
+    ) -> Option<T> {
+        info!(stage = name, "Stage began");
+        let began = Instant::now();
+        let result = stage.await;
+        let elapsed = began.elapsed();
+        match result {
+            Ok(r) => {
+                info!(stage = name, ?elapsed, "Stage done");
+                self.0.push((name, elapsed, StageResult::Done));
+                Some(r)
+            }
+            Err(e) => match e.downcast_ref::<CheckError>() {
+                Some(CheckError::Unsupported(_)) => {
+                    warn!(stage = name, %e, "Stage skipped");
+                    self.0
+                        .push((name, elapsed, StageResult::Skipped(e.to_string())));
+                    None
+                }
+                _ => {
+                    error!(stage = name, ?elapsed, ?e, "Stage failed");
+                    self.0
+                        .push((name, elapsed, StageResult::Failed(format!("{e:#}"))));
+                    None
+                }
+            },
+        }
+    }
+
+    fn failed(&self) -> Option<&'static str> {
+        self.0
+            .iter()
+            .find(|(_, _, r)| matches!(r, StageResult::Failed(_)))
+            .map(|(name, _, _)| *name)
+    }
+
+    /// Mark the last stage interrupted if benchmark of it finished early, remaining stages should be skipped then.
+    fn interrupt(&mut self, summary: &Summary) -> bool {
+        let (Some(reason), Some((name, _, result))) = (summary.interrupted, self.0.last_mut())
+        else {
+            return false;
+        };
+        warn!(
+            stage = *name,
+            reason, "Stage interrupted, remaining stages are skipped"
+        );
+        *result = StageResult::Interrupted(reason);
+        true
+    }
+
+    fn interrupted(&self) -> Option<(&'static str, &'static str)> {
+        self.0.iter().find_map(|(name, _, r)| match r {
+            StageResult::Interrupted(reason) => Some((*name, *reason)),
+            _ => None,
+        })
+    }
+
+    fn report(&self, summary: Option<&Summary>) {
+        info!("========== TPC-C run report ==========");
+        for (name, elapsed, result) in &self.0 {
+            match result {
+                StageResult::Done => info!(stage = name, ?elapsed, "done"),
+                StageResult::Skipped(reason) => warn!(stage = name, reason, "skipped"),
+                StageResult::Failed(reason) => error!(stage = name, ?elapsed, reason, "failed"),
+                StageResult::Interrupted(reason) => {
+                    warn!(stage = name, ?elapsed, reason, "interrupted")
+                }
+            }
+        }
+        if let Some(summary) = summary {
+            info!(
+                total_new_orders = summary.new_orders,
+                total_transactions = summary.transactions,
+                minutes = summary.minutes,
+                interrupted = summary.interrupted.is_some(),
+                tpmC_NewOrder = summary.tpmc_new_order,
+                tpmTOTAL = summary.tpm_total,
+                aborted = summary.aborted,
+                "Result"
+            );
+        }
+    }
+}
+
+/// Destroy(if exists), build, verify, warm up, benchmark, check and report, an error is returned if any stage
+/// failed or benchmark was interrupted, stages after the interrupted one are skipped.
+pub async fn run(
+    sut: Rc<Box<dyn Sut>>,
+    cfg: &BenchConfig,
+    from: Option<&Path>,
+    warm_up: Option<Duration>,
+    control: Arc<Control>,
+) -> anyhow::Result<Summary> {
+    let mut stages = Stages::default();
+    let warehouses = cfg.loader.warehouse as usize;
+    let scale = &cfg.loader.scale;
+
+    info!("Destroying schema if exists...");
+    if let Err(e) = sut.destroy_schema().await {
+        warn!(?e, "Could not destroy schema, maybe it does not exist");
+    }
+    let summary = async {
+        stages
+            .run("build", build(sut.clone(), cfg, from, &control))
+            .await?;
+        stages.run("verify load", sut.verify_load()).await;
+        stages.failed().is_none().then_some(())?;
+        if let Some(warm_up) = warm_up {
+            let mut tpcc = cfg.benchmark.tpcc.clone();
+            tpcc.ramp_up = Duration::ZERO;
+            tpcc.baking = warm_up;
+            let summary = stages
+                .run(
+                    "warm up",
+                    benchmark::benchmark(warehouses, scale, sut.clone(), &tpcc, control.clone()),
+                )
+                .await?;
+            if stages.interrupt(&summary) {
+                return None;
+            }
+        }
+        let summary = stages
+            .run(
+                "benchmark",
+                benchmark::benchmark(
+                    warehouses,
+                    scale,
+                    sut.clone(),
+                    &cfg.benchmark.tpcc,
+                    control.clone(),
+                ),
+            )
+            .await?;
+        if stages.interrupt(&summary) {
+            return Some(summary);
+        }
+        stages
+            .run("consistency check", sut.check_consistency())
+            .await;
+        Some(summary)
+    }
+    .await;
+
+    stages.report(summary.as_ref());
+    if let Some(stage) = stages.failed() {
+        return Err(anyhow!("Stage {stage} failed"));
+    }
+    if let Some((stage, reason)) = stages.interrupted() {
+        return Err(anyhow!("Stage {stage} interrupted by {reason}"));
+    }
+    summary.ok_or_else(|| anyhow!("Benchmark did not finish"))
+}
+
+/// Result of benchmarking at an isolation level.
+struct LevelResult {
+    level: IsolationLevel,
+    summary: Result<Summary, String>,
+    consistency: Result<(), String>,
+}
+
+/// Benchmark at every isolation level in turn and report them side by side, schema is rebuilt before every level
+/// if `rebuild`, otherwise levels share the built one. An error is returned if any level failed.
+pub async fn sweep(
+    sut: Rc<Box<dyn Sut>>,
+    cfg: &BenchConfig,
+    levels: &[IsolationLevel],
+    rebuild: bool,
+    control: Arc<Control>,
+) -> anyhow::Result<()> {
+    let mut results = vec![];
+    for &level in levels {
+        info!(%level, "Benchmarking at isolation level...");
+        if rebuild {
+            if let Err(e) = sut.destroy_schema().await {
+                warn!(?e, "Could not destroy schema, maybe it does not exist");
+            }
+            if let Err(e) = build(sut.clone(), cfg, None, &control).await {
+                error!(%level, ?e, "Could not rebuild schema");
+                results.push(LevelResult {
+                    level,
+                    summary: Err(format!("rebuilding failed: {e:#}")),
+                    consistency: Err("not checked".to_string()),
+                });
+                continue;
+            }
+        }
+        let mut tpcc = cfg.benchmark.tpcc.clone();
+        tpcc.isolation = Some(level);
+        let summary = benchmark::benchmark(
+            cfg.loader.warehouse as _,
+            &cfg.loader.scale,
+            sut.clone(),
+            &tpcc,
+            control.clone(),
+        )
+        .await
+        .map_err(|e| format!("{e:#}"));
+        let consistency = match sut.check_consistency().await {
+            Ok(()) => Ok(()),
+            Err(e) => Err(e.to_string()),
+        };
+        let interrupted = matches!(&summary, Ok(summary) if summary.interrupted.is_some());
+        results.push(LevelResult {
+            level,
+            summary,
+            consistency,
+        });
+        if interrupted {
+            warn!("Sweep interrupted, remaining isolation levels are skipped");
+            break;
+        }
+    }
+
+    info!("========== Isolation level sweep report ==========");
+    let mut failed = 0;
+    for LevelResult {
+        level,
+        summary,
+        consistency,
+    } in &results
+    {
+        let consistency = match consistency {
+            Ok(()) => "passed",
+            Err(e) => e.as_str(),
+        };
+        match summary {
+            Ok(summary) => info!(
+                %level,
+                tpmC_NewOrder = summary.tpmc_new_order,
+                tpmTOTAL = summary.tpm_total,
+                aborted = summary.aborted,
+                abort_rate = summary.abort_rate(),
+                interrupted = summary.interrupted.is_some(),
+                consistency,
+            ),
+            Err(reason) => {
+                failed += 1;
+                error!(%level, reason, consistency, "failed");
+            }
+        }
+    }
+    match failed {
+        0 => Ok(()),
+        failed => Err(anyhow!("Benchmark failed at {failed} isolation levels")),
+    }
+}